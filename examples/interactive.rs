@@ -0,0 +1,198 @@
+//! Play tetris in the terminal, using the library as the game engine.
+//!
+//! ```text
+//! cargo run --example interactive --features rand
+//! ```
+//!
+//! ←/→ move, ↑ rotates, ↓ soft drops, space hard drops, and q or Esc quits.
+
+use crossterm::{
+    cursor::{Hide, MoveTo, Show},
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
+    execute, queue,
+    style::Print,
+    terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use std::{
+    io::{self, Write},
+    time::{Duration, Instant},
+};
+use tetris::{
+    is_occupied, kicks::Orientation, queue::PieceQueue, random::SevenBag, tetromino, BlockShape,
+    Grid,
+};
+
+const WIDTH: usize = 10;
+const HEIGHT: usize = 20;
+const SPAWN_COLUMN: usize = 3;
+const GRAVITY: Duration = Duration::from_millis(500);
+
+type Board = Grid<WIDTH, HEIGHT>;
+
+/// The falling piece, drawn on an otherwise empty board.
+struct Falling {
+    piece: Board,
+    column: usize,
+    orientation: Orientation,
+}
+
+impl Falling {
+    fn spawn(shape: BlockShape) -> Self {
+        Self {
+            piece: tetromino::<WIDTH, HEIGHT>(shape).shift_by(0, SPAWN_COLUMN as isize),
+            column: SPAWN_COLUMN,
+            orientation: Orientation::of_tetromino(shape),
+        }
+    }
+
+    /// Move by the given offset, if the piece stays on the board and doesn't collide.
+    fn try_shift(&mut self, board: &Board, d_row: isize, d_col: isize) -> bool {
+        match self.piece.checked_shift_by(d_row, d_col) {
+            Some(moved) if board.can_place(&moved) => {
+                self.piece = moved;
+                self.column = self.column.saturating_add_signed(d_col);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn try_rotate(&mut self, board: &Board) {
+        if let Some((rotated, column)) =
+            self.piece
+                .rotate_cw_kicked_from(board, self.column, self.orientation)
+        {
+            self.piece = rotated;
+            self.column = column;
+            self.orientation = self.orientation.cw();
+        }
+    }
+}
+
+struct Game {
+    board: Board,
+    falling: Falling,
+    queue: PieceQueue<SevenBag<rand::rngs::ThreadRng>>,
+    lines: usize,
+}
+
+impl Game {
+    fn new() -> Self {
+        let mut queue = PieceQueue::new(SevenBag::new(rand::thread_rng()), 3);
+        Self {
+            board: Board::default(),
+            falling: Falling::spawn(queue.pop().expect("the bag never runs out")),
+            queue,
+            lines: 0,
+        }
+    }
+
+    /// Drop the falling piece to the stack, clear rows, and spawn the next piece.
+    /// Returns `false` when the game is over.
+    fn lock(&mut self) -> bool {
+        let Some(mut landed) = self.board.drop(self.falling.piece) else {
+            return false;
+        };
+        self.lines += landed.clear_solid_rows();
+        self.board = landed;
+        self.falling = Falling::spawn(self.queue.pop().expect("the bag never runs out"));
+        self.board.can_place(&self.falling.piece)
+    }
+
+    /// Move the piece down a row, locking it if it can't fall any further.
+    fn tick(&mut self) -> bool {
+        self.falling.try_shift(&self.board, 1, 0) || self.lock()
+    }
+
+    fn render(&self, out: &mut impl Write) -> io::Result<()> {
+        let ghost = self.board.ghost(self.falling.piece).unwrap_or_default();
+        let piece = self.falling.piece;
+        for (row_ix, ((row, ghost_row), piece_row)) in self
+            .board
+            .rows
+            .iter()
+            .zip(&ghost.rows)
+            .zip(&piece.rows)
+            .enumerate()
+        {
+            let line = (0..WIDTH)
+                .map(|col_ix| {
+                    match (
+                        is_occupied(&row[col_ix]),
+                        is_occupied(&piece_row[col_ix]),
+                        is_occupied(&ghost_row[col_ix]),
+                    ) {
+                        (true, _, _) => "[]",
+                        (_, true, _) => "##",
+                        (_, _, true) => "::",
+                        _ => " .",
+                    }
+                })
+                .collect::<String>();
+            queue!(out, MoveTo(0, row_ix as u16), Print(format!("|{line}|")))?;
+        }
+        queue!(
+            out,
+            MoveTo(0, HEIGHT as u16),
+            Print(format!("+{}+", "-".repeat(WIDTH * 2))),
+            MoveTo(WIDTH as u16 * 2 + 4, 0),
+            Print(format!("lines: {}", self.lines)),
+            MoveTo(WIDTH as u16 * 2 + 4, 2),
+            Print(format!("next: {:?}", self.queue.peek(3))),
+        )?;
+        out.flush()
+    }
+}
+
+fn play(out: &mut impl Write) -> io::Result<usize> {
+    let mut game = Game::new();
+    let mut next_tick = Instant::now() + GRAVITY;
+    loop {
+        game.render(out)?;
+        let timeout = next_tick.saturating_duration_since(Instant::now());
+        if event::poll(timeout)? {
+            let Event::Key(KeyEvent {
+                code,
+                kind: KeyEventKind::Press,
+                ..
+            }) = event::read()?
+            else {
+                continue;
+            };
+            let alive = match code {
+                KeyCode::Left | KeyCode::Right => {
+                    let d_col = if code == KeyCode::Left { -1 } else { 1 };
+                    game.falling.try_shift(&game.board, 0, d_col);
+                    true
+                }
+                KeyCode::Up => {
+                    game.falling.try_rotate(&game.board);
+                    true
+                }
+                KeyCode::Down => game.tick(),
+                KeyCode::Char(' ') => game.lock(),
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(game.lines),
+                _ => true,
+            };
+            if !alive {
+                return Ok(game.lines);
+            }
+        } else {
+            if !game.tick() {
+                return Ok(game.lines);
+            }
+            next_tick = Instant::now() + GRAVITY;
+        }
+    }
+}
+
+fn main() -> io::Result<()> {
+    let mut stdout = io::stdout();
+    terminal::enable_raw_mode()?;
+    execute!(stdout, EnterAlternateScreen, Hide, Clear(ClearType::All))?;
+    let result = play(&mut stdout);
+    execute!(stdout, Show, LeaveAlternateScreen)?;
+    terminal::disable_raw_mode()?;
+    println!("game over: {} lines", result?);
+    Ok(())
+}
@@ -0,0 +1,294 @@
+use crate::{CellState, Grid};
+
+/// Failure to read a [Grid] from text.
+#[derive(Debug, thiserror::Error, PartialEq, Eq, Clone)]
+pub enum ParseGridError {
+    #[error(
+        "unexpected character {char:?} at row {row_ix}, column {col_ix} (expected {empty:?} or {occupied:?})"
+    )]
+    UnexpectedChar {
+        row_ix: usize,
+        col_ix: usize,
+        char: char,
+        empty: char,
+        occupied: char,
+    },
+    #[error("row {row_ix} has {width} cells, but the grid is only {max} wide")]
+    RowTooWide {
+        row_ix: usize,
+        width: usize,
+        max: usize,
+    },
+    #[error("found {height} rows, but the grid is only {max} high")]
+    TooManyRows { height: usize, max: usize },
+    #[error("found {actual} cells, but the grid has {expected}")]
+    WrongLength { expected: usize, actual: usize },
+}
+
+impl<const WIDTH: usize, const HEIGHT: usize> Grid<WIDTH, HEIGHT, CellState> {
+    /// Leniently read a grid drawn with `.` and `#`, as in [grid!](crate::grid).
+    /// - Whitespace between cells is ignored, as are blank lines above and below the drawing.
+    /// - Short rows are padded on the right with empty cells.
+    /// - If there are fewer than `HEIGHT` rows, the drawing sits at the bottom of the grid.
+    /// ```
+    /// use tetris::{grid, Grid};
+    /// let art = "
+    ///     . #
+    ///     . # # #
+    /// ";
+    /// assert_eq!(
+    ///     Grid::from_ascii_art(art),
+    ///     Ok(grid![
+    ///         [. . . .],
+    ///         [. # . .],
+    ///         [. # # #],
+    ///     ])
+    /// );
+    /// ```
+    pub fn from_ascii_art(s: &str) -> Result<Self, ParseGridError> {
+        Self::from_ascii_with(s, '.', '#')
+    }
+
+    /// As [Grid::from_ascii_art], but with `empty` and `occupied` in place of `.` and `#`,
+    /// for reading other text formats.
+    /// If either is whitespace, then that whitespace is read as cells rather than ignored.
+    /// ```
+    /// use tetris::{grid, Grid};
+    /// assert_eq!(
+    ///     Grid::from_ascii_with("  X\nXXX", ' ', 'X'),
+    ///     Ok(grid![
+    ///         [. . #],
+    ///         [# # #],
+    ///     ])
+    /// );
+    /// ```
+    pub fn from_ascii_with(s: &str, empty: char, occupied: char) -> Result<Self, ParseGridError> {
+        let is_cell = |c: &char| *c == empty || *c == occupied || !c.is_whitespace();
+        let mut lines = s.lines().collect::<Vec<_>>();
+        while lines
+            .last()
+            .is_some_and(|line| !line.chars().any(|c| is_cell(&c)))
+        {
+            lines.pop();
+        }
+        let first_line = lines
+            .iter()
+            .position(|line| line.chars().any(|c| is_cell(&c)))
+            .unwrap_or(lines.len());
+        let lines = &lines[first_line..];
+        if lines.len() > HEIGHT {
+            return Err(ParseGridError::TooManyRows {
+                height: lines.len(),
+                max: HEIGHT,
+            });
+        }
+        let mut grid = Self::default();
+        let first_row_ix = HEIGHT - lines.len();
+        for (row_ix, line) in (first_row_ix..).zip(lines) {
+            for (col_ix, c) in line.chars().filter(is_cell).enumerate() {
+                let cell = match c {
+                    c if c == empty => CellState::Unoccupied,
+                    c if c == occupied => CellState::Occupied,
+                    char => {
+                        return Err(ParseGridError::UnexpectedChar {
+                            row_ix,
+                            col_ix,
+                            char,
+                            empty,
+                            occupied,
+                        })
+                    }
+                };
+                match grid.rows[row_ix].get_mut(col_ix) {
+                    Some(slot) => *slot = cell,
+                    None => {
+                        return Err(ParseGridError::RowTooWide {
+                            row_ix,
+                            width: line.chars().filter(is_cell).count(),
+                            max: WIDTH,
+                        })
+                    }
+                }
+            }
+        }
+        Ok(grid)
+    }
+
+    /// Read a grid from exactly `WIDTH * HEIGHT` `.`s and `#`s in row-major order, with no separators,
+    /// as exchanged by other tetris tools.
+    /// ```
+    /// use tetris::{grid, Grid};
+    /// assert_eq!(Grid::from_flat("...#.###"), Ok(grid![
+    ///     [. . . #],
+    ///     [. # # #],
+    /// ]));
+    /// ```
+    pub fn from_flat(s: &str) -> Result<Self, ParseGridError> {
+        let actual = s.chars().count();
+        if actual != WIDTH * HEIGHT {
+            return Err(ParseGridError::WrongLength {
+                expected: WIDTH * HEIGHT,
+                actual,
+            });
+        }
+        let mut grid = Self::default();
+        for (ix, c) in s.chars().enumerate() {
+            let (row_ix, col_ix) = (ix / WIDTH, ix % WIDTH);
+            grid.rows[row_ix][col_ix] = match c {
+                '.' => CellState::Unoccupied,
+                '#' => CellState::Occupied,
+                char => {
+                    return Err(ParseGridError::UnexpectedChar {
+                        row_ix,
+                        col_ix,
+                        char,
+                        empty: '.',
+                        occupied: '#',
+                    })
+                }
+            };
+        }
+        Ok(grid)
+    }
+
+    /// The inverse of [Grid::from_flat].
+    pub fn to_flat(&self) -> String {
+        self.rows
+            .iter()
+            .flatten()
+            .map(|cell| match cell {
+                CellState::Occupied => '#',
+                CellState::Unoccupied => '.',
+            })
+            .collect()
+    }
+
+    /// Draw `self` and `other` side by side, followed by a third drawing where cells that differ are `X`.
+    /// Useful in assertion messages.
+    /// ```
+    /// use tetris::grid;
+    /// let left = grid![
+    ///     [. . .],
+    ///     [. # #],
+    /// ];
+    /// let right = grid![
+    ///     [. . .],
+    ///     [. # .],
+    /// ];
+    /// assert_eq!(
+    ///     left.diff_string(&right),
+    ///     "\
+    /// ... | ... | ...
+    /// .## | .#. | .#X
+    /// "
+    /// );
+    /// ```
+    pub fn diff_string(&self, other: &Self) -> String {
+        let draw = |cell| match cell {
+            CellState::Occupied => '#',
+            CellState::Unoccupied => '.',
+        };
+        let mut s = String::new();
+        for (lhs, rhs) in self.rows.iter().zip(&other.rows) {
+            s.extend(lhs.iter().copied().map(draw));
+            s.push_str(" | ");
+            s.extend(rhs.iter().copied().map(draw));
+            s.push_str(" | ");
+            s.extend(lhs.iter().zip(rhs).map(|(&lhs, &rhs)| match lhs == rhs {
+                true => draw(lhs),
+                false => 'X',
+            }));
+            s.push('\n');
+        }
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid;
+
+    #[test]
+    fn ragged_fixture() {
+        let fixture = "
+
+            . . # #
+            #
+            # # . # #
+
+        ";
+        assert_eq!(
+            Grid::from_ascii_art(fixture),
+            Ok(grid![
+                [. . . . .],
+                [. . # # .],
+                [# . . . .],
+                [# # . # #],
+            ])
+        );
+    }
+
+    #[test]
+    fn too_wide_and_too_tall() {
+        assert_eq!(
+            Grid::<2, 2>::from_ascii_art("# . #"),
+            Err(ParseGridError::RowTooWide {
+                row_ix: 1,
+                width: 3,
+                max: 2
+            })
+        );
+        assert_eq!(
+            Grid::<2, 2>::from_ascii_art("#\n#\n#"),
+            Err(ParseGridError::TooManyRows { height: 3, max: 2 })
+        );
+    }
+
+    #[test]
+    fn zeros_and_ones() {
+        let board = "
+            0000
+            0110
+            1101
+        ";
+        assert_eq!(
+            Grid::from_ascii_with(board, '0', '1'),
+            Ok(grid![
+                [. . . .],
+                [. # # .],
+                [# # . #],
+            ])
+        );
+        assert_eq!(
+            Grid::<4, 3>::from_ascii_with(board, '.', '#'),
+            Err(ParseGridError::UnexpectedChar {
+                row_ix: 0,
+                col_ix: 0,
+                char: '0',
+                empty: '.',
+                occupied: '#'
+            })
+        );
+    }
+
+    #[test]
+    fn flat_round_trip() {
+        let board: Grid<10, 4> = grid![
+            [. . . . . . . . . .],
+            [. . . . # . . . . .],
+            [# # . . # # . . . #],
+            [# # # . # # # # # #],
+        ];
+        let flat = board.to_flat();
+        assert_eq!(flat.len(), 40);
+        assert_eq!(Grid::from_flat(&flat), Ok(board));
+        assert_eq!(
+            Grid::<10, 4>::from_flat(&flat[1..]),
+            Err(ParseGridError::WrongLength {
+                expected: 40,
+                actual: 39
+            })
+        );
+    }
+}
@@ -0,0 +1,218 @@
+//! A bit-packed occupancy board, for when cell contents don't matter and speed does.
+//!
+//! A [BitGrid] is semantically a [Grid] of [CellState], and supports the same tetris operations,
+//! but stores each row as a [u128] bitmask, so that e.g checking for a full row is a single comparison.
+//! `WIDTH` may be at most 128.
+
+use crate::{CellState, Grid, WouldClobber};
+use core::ops;
+
+/// A [Grid] of [CellState], packed one bit per cell.
+/// See [module documentation](self) for more.
+// choice: column 0 is the most significant of the WIDTH bits, so that `>>` scrolls right, like `Grid`'s `Shr`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitGrid<const WIDTH: usize, const HEIGHT: usize> {
+    rows: [u128; HEIGHT],
+}
+
+impl<const WIDTH: usize, const HEIGHT: usize> Default for BitGrid<WIDTH, HEIGHT> {
+    fn default() -> Self {
+        Self { rows: [0; HEIGHT] }
+    }
+}
+
+impl<const WIDTH: usize, const HEIGHT: usize> BitGrid<WIDTH, HEIGHT> {
+    /// A row with every cell occupied.
+    const FULL_ROW: u128 = {
+        assert!(WIDTH <= 128, "BitGrid rows are at most 128 cells wide");
+        match WIDTH {
+            0 => 0,
+            _ => u128::MAX >> (128 - WIDTH),
+        }
+    };
+
+    fn bit(col_ix: usize) -> u128 {
+        (1 << (WIDTH - 1 - col_ix)) & Self::FULL_ROW
+    }
+
+    pub fn is_occupied(&self, row_ix: usize, col_ix: usize) -> bool {
+        self.rows[row_ix] & Self::bit(col_ix) != 0
+    }
+
+    /// See [Grid::try_bump_down].
+    pub fn try_bump_down(mut self) -> Option<Self> {
+        match self.rows.last() {
+            Some(0) => {
+                self.rows.rotate_right(1);
+                Some(self)
+            }
+            Some(_) => None,
+            None => Some(self),
+        }
+    }
+
+    /// See [Grid::try_shift_down].
+    pub fn try_shift_down(mut self, by: usize) -> Option<Self> {
+        for _ in 0..by {
+            self = self.try_bump_down()?
+        }
+        Some(self)
+    }
+
+    /// See [Grid::drop].
+    pub fn drop(self, rhs: Self) -> Option<Self> {
+        let mut furthest = (self & rhs).ok()?;
+
+        // bound by HEIGHT to catch an empty rhs
+        for shift in 0..HEIGHT {
+            match rhs.try_shift_down(shift) {
+                Some(shifted) => match self & shifted {
+                    Ok(new_furthest) => furthest = new_furthest,
+                    Err(_) => break,
+                },
+                None => break, // rhs has hit the bottom of the grid
+            }
+        }
+        Some(furthest)
+    }
+
+    /// See [Grid::with_solid_rows_cleared].
+    pub fn with_solid_rows_cleared(mut self) -> Self {
+        // compact non-full rows towards the bottom
+        let mut write_ix = HEIGHT;
+        for read_ix in (0..HEIGHT).rev() {
+            if self.rows[read_ix] != Self::FULL_ROW {
+                write_ix -= 1;
+                self.rows[write_ix] = self.rows[read_ix];
+            }
+        }
+        self.rows[..write_ix].fill(0);
+        self
+    }
+}
+
+/// See [Grid]'s [ops::BitAnd].
+impl<const WIDTH: usize, const HEIGHT: usize> ops::BitAnd<Self> for BitGrid<WIDTH, HEIGHT> {
+    type Output = Result<Self, WouldClobber>;
+
+    fn bitand(mut self, rhs: Self) -> Self::Output {
+        for (row_ix, (lhs, rhs)) in self.rows.iter_mut().zip(rhs.rows).enumerate() {
+            let clobbered = *lhs & rhs;
+            if clobbered != 0 {
+                return Err(WouldClobber {
+                    row_ix,
+                    // highest bit is the leftmost column
+                    col_ix: WIDTH - 1 - (127 - clobbered.leading_zeros() as usize),
+                });
+            }
+            *lhs |= rhs
+        }
+        Ok(self)
+    }
+}
+
+/// See [Grid]'s [ops::Shr].
+impl<const WIDTH: usize, const HEIGHT: usize> ops::Shr<usize> for BitGrid<WIDTH, HEIGHT> {
+    type Output = Self;
+
+    fn shr(mut self, rhs: usize) -> Self::Output {
+        for row in self.rows.iter_mut() {
+            *row = row.checked_shr(rhs as u32).unwrap_or(0)
+        }
+        self
+    }
+}
+
+impl<const WIDTH: usize, const HEIGHT: usize> From<Grid<WIDTH, HEIGHT, CellState>>
+    for BitGrid<WIDTH, HEIGHT>
+{
+    fn from(grid: Grid<WIDTH, HEIGHT, CellState>) -> Self {
+        let mut rows = [0; HEIGHT];
+        for (bits, row) in rows.iter_mut().zip(grid.rows) {
+            for (col_ix, cell) in row.into_iter().enumerate() {
+                if cell == CellState::Occupied {
+                    *bits |= Self::bit(col_ix)
+                }
+            }
+        }
+        Self { rows }
+    }
+}
+
+impl<const WIDTH: usize, const HEIGHT: usize> From<BitGrid<WIDTH, HEIGHT>>
+    for Grid<WIDTH, HEIGHT, CellState>
+{
+    fn from(bit_grid: BitGrid<WIDTH, HEIGHT>) -> Self {
+        let mut grid = Grid::default();
+        for (row_ix, row) in grid.rows.iter_mut().enumerate() {
+            for (col_ix, cell) in row.iter_mut().enumerate() {
+                if bit_grid.is_occupied(row_ix, col_ix) {
+                    *cell = CellState::Occupied
+                }
+            }
+        }
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid;
+    use proptest::prelude::*;
+
+    #[test]
+    fn round_trip() {
+        let grid = grid![
+            [# . . .],
+            [. . # #],
+        ];
+        assert_eq!(Grid::from(BitGrid::from(grid)), grid);
+    }
+
+    #[test]
+    fn shr_discards_rightmost_column() {
+        let shifted = BitGrid::from(grid![[# . #]]) >> 1;
+        assert_eq!(Grid::from(shifted), grid![[. # .]]);
+    }
+
+    #[test]
+    fn clobbering() {
+        assert_eq!(
+            BitGrid::from(grid![
+                [. . . .],
+                [. # # .],
+            ]) & BitGrid::from(grid![
+                [. . . .],
+                [. . # #],
+            ]),
+            Err(WouldClobber {
+                row_ix: 1,
+                col_ix: 2
+            })
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn agrees_with_grid_on_clears(bools in any::<[[bool; 4]; 6]>()) {
+            let grid = Grid::<4, 6>::from(bools);
+            prop_assert_eq!(
+                Grid::from(BitGrid::from(grid).with_solid_rows_cleared()),
+                grid.with_solid_rows_cleared()
+            );
+        }
+
+        #[test]
+        fn agrees_with_grid_on_drop(board in any::<[[bool; 4]; 6]>(), piece in any::<[[bool; 4]; 2]>()) {
+            let board = Grid::<4, 6>::from(board);
+            let mut piece_rows = [[false; 4]; 6];
+            piece_rows[..2].copy_from_slice(&piece);
+            let piece = Grid::<4, 6>::from(piece_rows);
+            prop_assert_eq!(
+                BitGrid::from(board).drop(BitGrid::from(piece)).map(Grid::from),
+                board.drop(piece)
+            );
+        }
+    }
+}
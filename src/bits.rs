@@ -0,0 +1,106 @@
+use crate::{is_occupied, CellState, Grid};
+
+/// Failure to read a [Grid] from [Grid::to_bits] output.
+#[derive(Debug, thiserror::Error, PartialEq, Eq, Clone, Copy)]
+pub enum FromBitsError {
+    #[error("expected {expected} bytes, got {actual}")]
+    WrongLength { expected: usize, actual: usize },
+    #[error("the padding bits in the final byte must be zero")]
+    NonZeroPadding,
+}
+
+impl<const WIDTH: usize, const HEIGHT: usize, CellT> Grid<WIDTH, HEIGHT, CellT>
+where
+    CellT: Default + PartialEq,
+{
+    /// Pack the occupancy of each cell into one bit, row by row, most significant bit first.
+    /// The final byte is padded with zeroes, so there are `ceil(WIDTH * HEIGHT / 8)` bytes.
+    /// ```
+    /// use tetris::grid;
+    /// assert_eq!(
+    ///     grid![
+    ///         [# . #],
+    ///         [. . #],
+    ///     ]
+    ///     .to_bits(),
+    ///     [0b101_001_00]
+    /// );
+    /// ```
+    pub fn to_bits(&self) -> Vec<u8> {
+        let mut bytes = vec![0; (WIDTH * HEIGHT).div_ceil(8)];
+        for (ix, cell) in self.rows.iter().flatten().enumerate() {
+            if is_occupied(cell) {
+                bytes[ix / 8] |= 0x80 >> (ix % 8)
+            }
+        }
+        bytes
+    }
+}
+
+impl<const WIDTH: usize, const HEIGHT: usize> Grid<WIDTH, HEIGHT, CellState> {
+    /// The inverse of [Grid::to_bits].
+    pub fn from_bits(bytes: &[u8]) -> Result<Self, FromBitsError> {
+        let cells = WIDTH * HEIGHT;
+        let expected = cells.div_ceil(8);
+        if bytes.len() != expected {
+            return Err(FromBitsError::WrongLength {
+                expected,
+                actual: bytes.len(),
+            });
+        }
+        let padding = expected * 8 - cells;
+        if bytes
+            .last()
+            .is_some_and(|last| last & ((1 << padding) - 1) != 0)
+        {
+            return Err(FromBitsError::NonZeroPadding);
+        }
+        let mut grid = Self::default();
+        for (ix, cell) in grid.rows.iter_mut().flatten().enumerate() {
+            if bytes[ix / 8] & (0x80 >> (ix % 8)) != 0 {
+                *cell = CellState::Occupied
+            }
+        }
+        Ok(grid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid;
+
+    #[test]
+    fn round_trip_10x4() {
+        let grid = grid![
+            [. . . . . . . . . #],
+            [. . # # . . . . . .],
+            [# . # # . # # # # #],
+            [# # # # # # # # . #],
+        ];
+        let bits = grid.to_bits();
+        // 40 cells fit exactly in 5 bytes
+        assert_eq!(bits.len(), 5);
+        assert_eq!(Grid::from_bits(&bits), Ok(grid));
+    }
+
+    #[test]
+    fn padding() {
+        let grid = grid![[# # #], [# # #], [# # #]];
+        let mut bits = grid.to_bits();
+        assert_eq!(bits, [0xff, 0b1000_0000]);
+        assert_eq!(Grid::from_bits(&bits), Ok(grid));
+        bits[1] |= 1;
+        assert_eq!(
+            Grid::<3, 3>::from_bits(&bits),
+            Err(FromBitsError::NonZeroPadding)
+        );
+        assert_eq!(
+            Grid::<3, 3>::from_bits(&bits[..1]),
+            Err(FromBitsError::WrongLength {
+                expected: 2,
+                actual: 1
+            })
+        );
+    }
+}
@@ -0,0 +1,238 @@
+//! Move generation and board evaluation, for writing bots.
+
+use crate::{is_occupied, tetromino, BlockShape, CellState, Grid};
+
+/// Rates a `WIDTH` × `HEIGHT` board, higher is better.
+pub trait Heuristic<const WIDTH: usize, const HEIGHT: usize> {
+    /// `board` has had a piece dropped on it, but full rows haven't been cleared yet.
+    fn score(&self, board: &Grid<WIDTH, HEIGHT, CellState>) -> f64;
+}
+
+/// A weighted sum of board features, including row transitions from Pierre Dellacherie's hand-tuned player.
+///
+/// Each weight multiplies its feature, so penalties should be negative.
+/// Features other than `lines_cleared` are measured after full rows have been cleared.
+///
+/// The default weights for aggregate height, holes, bumpiness and lines cleared are Yiyuan Lee's,
+/// found with a genetic algorithm. Row transitions, which Lee's player doesn't use, are lightly penalised.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dellacherie {
+    pub aggregate_height: f64,
+    pub holes: f64,
+    pub bumpiness: f64,
+    pub row_transitions: f64,
+    pub lines_cleared: f64,
+}
+
+impl Default for Dellacherie {
+    fn default() -> Self {
+        Self {
+            aggregate_height: -0.51,
+            holes: -0.36,
+            bumpiness: -0.18,
+            row_transitions: -0.1,
+            lines_cleared: 0.76,
+        }
+    }
+}
+
+impl<const WIDTH: usize, const HEIGHT: usize> Heuristic<WIDTH, HEIGHT> for Dellacherie {
+    fn score(&self, board: &Grid<WIDTH, HEIGHT, CellState>) -> f64 {
+        let lines_cleared = board.clear_mask().into_iter().filter(|&full| full).count();
+        let board = board.with_solid_rows_cleared();
+        self.aggregate_height * board.aggregate_height() as f64
+            + self.holes * board.holes() as f64
+            + self.bumpiness * board.bumpiness() as f64
+            + self.row_transitions * board.row_transitions() as f64
+            + self.lines_cleared * lines_cleared as f64
+    }
+}
+
+impl<const WIDTH: usize, const HEIGHT: usize> Grid<WIDTH, HEIGHT, CellState> {
+    /// Every way of dropping `shape` onto this grid, as `(column, rotation, resulting grid)`.
+    ///
+    /// `rotation` indexes the shape's [distinct rotations](Grid::distinct_rotations), starting from spawn,
+    /// and `column` is the left of the rotated shape's bounding box.
+    /// Rows are not cleared.
+    pub fn legal_placements(&self, shape: BlockShape) -> Vec<(usize, usize, Self)> {
+        let mut placements = Vec::new();
+        for (rotation, piece) in tetromino::<4, 4>(shape)
+            .distinct_rotations()
+            .into_iter()
+            .enumerate()
+        {
+            let Some(piece) = resize(piece) else {
+                continue;
+            };
+            let Some(bbox) = piece.bounding_box() else {
+                continue;
+            };
+            for col in 0..=WIDTH - bbox.width {
+                if let Some(landed) = self.drop(piece >> col) {
+                    placements.push((col, rotation, landed))
+                }
+            }
+        }
+        placements
+    }
+
+    /// The [legal placement](Grid::legal_placements) of `shape` which `heuristic` scores highest.
+    /// Ties go to the first placement. Rows are not cleared.
+    pub fn best_placement(
+        &self,
+        shape: BlockShape,
+        heuristic: &(impl Heuristic<WIDTH, HEIGHT> + ?Sized),
+    ) -> Option<(usize, usize, Self)> {
+        self.legal_placements(shape)
+            .into_iter()
+            .map(|placement| (heuristic.score(&placement.2), placement))
+            .reduce(|best, next| if next.0 > best.0 { next } else { best })
+            .map(|(_, placement)| placement)
+    }
+
+    /// Search for a sequence of placements of `pieces`, in order, which leaves the board empty,
+    /// as `(shape, column, rotation)` per [Grid::legal_placements].
+    ///
+    /// At most `max_depth` pieces are placed. Rows are cleared after each placement.
+    /// Returns [None] if there's no perfect clear within that many pieces.
+    pub fn find_perfect_clears(
+        &self,
+        pieces: &[BlockShape],
+        max_depth: usize,
+    ) -> Option<Vec<(BlockShape, usize, usize)>> {
+        if self.is_empty_board() {
+            return Some(Vec::new());
+        }
+        let (&shape, rest) = pieces.split_first()?;
+        if max_depth == 0 {
+            return None;
+        }
+        for (col, rotation, landed) in self.legal_placements(shape) {
+            if let Some(mut moves) = landed
+                .with_solid_rows_cleared()
+                .find_perfect_clears(rest, max_depth - 1)
+            {
+                moves.insert(0, (shape, col, rotation));
+                return Some(moves);
+            }
+        }
+        None
+    }
+}
+
+/// Copy a piece into the top-left of a differently sized grid, if it fits.
+fn resize<
+    const FROM_WIDTH: usize,
+    const FROM_HEIGHT: usize,
+    const WIDTH: usize,
+    const HEIGHT: usize,
+>(
+    piece: Grid<FROM_WIDTH, FROM_HEIGHT, CellState>,
+) -> Option<Grid<WIDTH, HEIGHT, CellState>> {
+    let mut resized = Grid::default();
+    for (row_ix, row) in piece.rows.into_iter().enumerate() {
+        for (col_ix, cell) in row.into_iter().enumerate() {
+            if is_occupied(&cell) {
+                *resized.rows.get_mut(row_ix)?.get_mut(col_ix)? = cell
+            }
+        }
+    }
+    Some(resized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid;
+
+    /// Move generation counts on an empty board, to catch regressions in the placement search.
+    #[test]
+    fn perft_empty_board() {
+        let board = Grid::<10, 20>::default();
+        assert_eq!(board.legal_placements(BlockShape::Q).len(), 9);
+        assert_eq!(
+            BlockShape::ALL
+                .into_iter()
+                .map(|shape| board.legal_placements(shape).len())
+                .sum::<usize>(),
+            162
+        );
+    }
+
+    #[test]
+    fn placements_land_on_the_stack() {
+        let board = grid![
+            [. . . .],
+            [. . . .],
+            [. . . .],
+            [# # # .],
+        ];
+        let placements = board.legal_placements(BlockShape::Q);
+        assert_eq!(
+            placements.last(),
+            Some(&(
+                2,
+                0,
+                grid![
+                    [. . . .],
+                    [. . # #],
+                    [. . # #],
+                    [# # # .],
+                ]
+            ))
+        );
+        assert_eq!(placements.len(), 3);
+    }
+
+    #[test]
+    fn two_piece_perfect_clear() {
+        let board = grid![
+            [. . . . . .],
+            [. . . . . .],
+            [# # . . . .],
+            [# # . . . .],
+        ];
+        let pieces = [BlockShape::Q, BlockShape::Q];
+        assert_eq!(
+            board.find_perfect_clears(&pieces, 2),
+            Some(vec![(BlockShape::Q, 2, 0), (BlockShape::Q, 4, 0)])
+        );
+        assert_eq!(board.find_perfect_clears(&pieces, 1), None);
+        assert_eq!(board.find_perfect_clears(&pieces[..1], 2), None);
+    }
+
+    #[test]
+    fn i_piece_clears_the_well() {
+        let (col, rotation, landed) = well()
+            .best_placement(BlockShape::I, &Dellacherie::default())
+            .unwrap();
+        assert_eq!((col, rotation), (3, 1));
+        assert!(landed.with_solid_rows_cleared().is_empty_board());
+    }
+
+    #[test]
+    fn heuristics_are_pluggable() {
+        struct Lowest;
+        impl Heuristic<4, 6> for Lowest {
+            fn score(&self, board: &Grid<4, 6, CellState>) -> f64 {
+                -(board.with_solid_rows_cleared().aggregate_height() as f64)
+            }
+        }
+        let heuristics: [&dyn Heuristic<4, 6>; 2] = [&Dellacherie::default(), &Lowest];
+        for heuristic in heuristics {
+            let (_, _, landed) = well().best_placement(BlockShape::I, heuristic).unwrap();
+            assert!(landed.with_solid_rows_cleared().is_empty_board());
+        }
+    }
+
+    fn well() -> Grid<4, 6, CellState> {
+        grid![
+            [. . . .],
+            [. . . .],
+            [# # # .],
+            [# # # .],
+            [# # # .],
+            [# # # .],
+        ]
+    }
+}
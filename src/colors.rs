@@ -0,0 +1,96 @@
+//! Coloured cells, enabled by the `colors` feature.
+//!
+//! This is a worked example of a custom `CellT`: [Color::Transparent] is the [Default], so it's the empty cell,
+//! and every other colour is occupied.
+
+use crate::BlockShape;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Color {
+    #[default]
+    Transparent,
+    Cyan,
+    Yellow,
+    Purple,
+    Green,
+    Red,
+    Blue,
+    Orange,
+}
+
+/// The guideline colour for each shape.
+/// ```
+/// use tetris::{colors::Color, is_occupied, tetromino, BlockShape};
+/// let piece = tetromino::<4, 4>(BlockShape::T).map(|cell| match is_occupied(&cell) {
+///     true => Color::from(BlockShape::T),
+///     false => Color::Transparent,
+/// });
+/// assert_eq!(piece.rows[0], [Color::Purple, Color::Purple, Color::Purple, Color::Transparent]);
+/// ```
+impl From<BlockShape> for Color {
+    fn from(shape: BlockShape) -> Self {
+        match shape {
+            BlockShape::I => Self::Cyan,
+            BlockShape::Q => Self::Yellow,
+            BlockShape::T => Self::Purple,
+            BlockShape::S => Self::Green,
+            BlockShape::Z => Self::Red,
+            BlockShape::J => Self::Blue,
+            BlockShape::L => Self::Orange,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Grid, WouldClobber};
+    use Color::{Blue, Red, Transparent as E, Yellow};
+
+    #[test]
+    fn drop_colored_piece() {
+        let board = Grid {
+            rows: [[E, E, E], [E, E, E], [Red, E, E]],
+        };
+        let piece = Grid {
+            rows: [[Yellow, Yellow, E], [E, E, E], [E, E, E]],
+        };
+        assert_eq!(
+            board.drop(piece),
+            Some(Grid {
+                rows: [[E, E, E], [Yellow, Yellow, E], [Red, E, E]],
+            })
+        );
+    }
+
+    #[test]
+    fn colors_clobber() {
+        let board = Grid { rows: [[E, Blue]] };
+        assert_eq!(
+            board & Grid { rows: [[E, Red]] },
+            Err(WouldClobber {
+                row_ix: 0,
+                col_ix: 1
+            })
+        );
+        assert_eq!(
+            board & Grid { rows: [[Red, E]] },
+            Ok(Grid {
+                rows: [[Red, Blue]]
+            })
+        );
+    }
+
+    #[test]
+    fn clear_multicolored_row() {
+        let board = Grid {
+            rows: [[E, Yellow, E], [Red, Blue, Yellow]],
+        };
+        assert_eq!(
+            board.with_solid_rows_cleared(),
+            Grid {
+                rows: [[E, E, E], [E, Yellow, E]],
+            }
+        );
+    }
+}
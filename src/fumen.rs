@@ -0,0 +1,151 @@
+//! Export to [fumen](https://harddrop.com/fumen/), the board editor commonly used for sharing tetris positions.
+//!
+//! Only a single page, with no piece and no comment, is encoded.
+
+use crate::{CellState, Grid};
+
+/// The editor's field is always this wide.
+pub const FIELD_WIDTH: usize = 10;
+/// The editor's visible field height, above a single garbage row.
+pub const FIELD_HEIGHT: usize = 23;
+const FIELD_CELLS: usize = FIELD_WIDTH * (FIELD_HEIGHT + 1);
+
+/// No piece, no rotation, at the origin, with guideline colours, locked, and without a comment.
+const ACTION: usize = 0b00100 * FIELD_CELLS * 4 * 8;
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// A cell which can be drawn in fumen.
+pub trait FumenCell {
+    /// The editor's block value: `0` is empty, `1..=7` are I, L, O, Z, T, J and S, and `8` is garbage.
+    fn fumen_value(&self) -> u8;
+}
+
+/// Occupied cells are drawn as garbage.
+impl FumenCell for CellState {
+    fn fumen_value(&self) -> u8 {
+        match self {
+            CellState::Occupied => 8,
+            CellState::Unoccupied => 0,
+        }
+    }
+}
+
+/// Cells are drawn as the piece of the same guideline colour.
+#[cfg(feature = "colors")]
+impl FumenCell for crate::colors::Color {
+    fn fumen_value(&self) -> u8 {
+        use crate::colors::Color;
+        match self {
+            Color::Transparent => 0,
+            Color::Cyan => 1,
+            Color::Orange => 2,
+            Color::Yellow => 3,
+            Color::Red => 4,
+            Color::Purple => 5,
+            Color::Blue => 6,
+            Color::Green => 7,
+        }
+    }
+}
+
+impl<const WIDTH: usize, const HEIGHT: usize, CellT> Grid<WIDTH, HEIGHT, CellT>
+where
+    CellT: FumenCell,
+{
+    /// Encode this board as fumen data, which the editor opens from e.g `https://harddrop.com/fumen/?<data>`.
+    /// The bottom of the grid is the bottom of the editor's field.
+    /// ```
+    /// use tetris::grid;
+    /// let board = grid![
+    ///     [. . . . . . . . . .],
+    ///     [# # # # . . . . . .],
+    /// ];
+    /// assert_eq!(board.to_fumen(), "v115@bhD8PeAgH");
+    /// ```
+    /// # Panics
+    /// - If `WIDTH` isn't [FIELD_WIDTH], or `HEIGHT` is greater than [FIELD_HEIGHT].
+    pub fn to_fumen(&self) -> String {
+        assert_eq!(WIDTH, FIELD_WIDTH, "fumen fields are {FIELD_WIDTH} wide");
+        assert!(
+            HEIGHT <= FIELD_HEIGHT,
+            "fumen fields are at most {FIELD_HEIGHT} high"
+        );
+        let mut field = [0; FIELD_CELLS];
+        let first_cell = (FIELD_HEIGHT - HEIGHT) * FIELD_WIDTH;
+        for ((row_ix, col_ix), cell) in self.cells() {
+            field[first_cell + row_ix * FIELD_WIDTH + col_ix] = cell.fumen_value();
+        }
+
+        let mut data = String::new();
+        // runs of the difference from the previous page, which is empty, offset by 8
+        let mut cells = field.iter().peekable();
+        while let Some(&value) = cells.next() {
+            let mut run = 1;
+            while cells.next_if_eq(&&value).is_some() {
+                run += 1;
+            }
+            push_base64(&mut data, (value as usize + 8) * FIELD_CELLS + run - 1, 2);
+        }
+        if field.iter().all(|&value| value == 0) {
+            // how many following pages repeat this empty field
+            push_base64(&mut data, 0, 1);
+        }
+        push_base64(&mut data, ACTION, 3);
+
+        // the editor expects a `?` after the first 42 characters, then every 47
+        let mut out = String::from("v115@");
+        for (ix, c) in data.chars().enumerate() {
+            if ix >= 42 && (ix - 42) % 47 == 0 {
+                out.push('?');
+            }
+            out.push(c);
+        }
+        out
+    }
+}
+
+/// Push `value` as `digits` little-endian base64 digits.
+fn push_base64(s: &mut String, mut value: usize, digits: usize) {
+    for _ in 0..digits {
+        s.push(BASE64[value % 64] as char);
+        value /= 64;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid;
+
+    // the editor's blank page
+    #[test]
+    fn empty_field() {
+        assert_eq!(Grid::<10, 4>::default().to_fumen(), "v115@vhAAgH");
+    }
+
+    #[test]
+    fn reference_board() {
+        // runs of 213 empty, 1 garbage, 6 empty, 3 garbage, 1 empty, 6 garbage, and the 10 empty garbage row
+        let board = grid![
+            [. . . # . . . . . .],
+            [# # # . # # # # # #],
+        ];
+        assert_eq!(board.to_fumen(), "v115@UhA8FeC8AeF8JeAgH");
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_narrow() {
+        Grid::<4, 4>::default().to_fumen();
+    }
+
+    #[test]
+    #[cfg(feature = "colors")]
+    fn colored_i_piece() {
+        use crate::colors::Color::{Cyan, Transparent as E};
+        let mut board = Grid::<10, 1, _>::default();
+        board.rows[0] = [Cyan, Cyan, Cyan, Cyan, E, E, E, E, E, E];
+        assert_eq!(board.to_fumen(), "v115@bhzhPeAgH");
+    }
+}
@@ -0,0 +1,98 @@
+//! The hold slot, where the player may stash one piece for later.
+
+use crate::BlockShape;
+
+/// A single-piece stash.
+///
+/// Each piece may only be held once: after a [Hold::swap], further swaps are refused until [Hold::reset]
+/// is called, which should happen when the next piece spawns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hold<Shape = BlockShape> {
+    current: Option<Shape>,
+    used: bool,
+}
+
+/// The outcome of a [Hold::swap].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Swap<Shape = BlockShape> {
+    /// The piece was stashed, and this is what was held before it, if anything.
+    Swapped(Option<Shape>),
+    /// The hold has already been used since the last [Hold::reset], so the piece is handed straight back.
+    Refused(Shape),
+}
+
+impl<Shape> Default for Hold<Shape> {
+    fn default() -> Self {
+        Self {
+            current: None,
+            used: false,
+        }
+    }
+}
+
+impl<Shape> Hold<Shape> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The piece currently in the hold, if any.
+    pub fn held(&self) -> Option<&Shape> {
+        self.current.as_ref()
+    }
+
+    /// Whether a [Hold::swap] would be accepted.
+    pub fn can_swap(&self) -> bool {
+        !self.used
+    }
+
+    /// Stash `incoming`, returning the previously held piece.
+    ///
+    /// If the hold has already been used since the last [Hold::reset], the hold is unchanged and
+    /// `incoming` is [refused](Swap::Refused).
+    /// ```
+    /// use tetris::{hold::{Hold, Swap}, BlockShape::{I, T}};
+    /// let mut hold = Hold::new();
+    /// assert_eq!(hold.swap(T), Swap::Swapped(None));
+    /// assert_eq!(hold.swap(I), Swap::Refused(I));
+    /// hold.reset();
+    /// assert_eq!(hold.swap(I), Swap::Swapped(Some(T)));
+    /// ```
+    pub fn swap(&mut self, incoming: Shape) -> Swap<Shape> {
+        if self.used {
+            return Swap::Refused(incoming);
+        }
+        self.used = true;
+        Swap::Swapped(self.current.replace(incoming))
+    }
+
+    /// Allow the hold to be used again. Call this once per spawned piece.
+    pub fn reset(&mut self) {
+        self.used = false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use BlockShape::{I, Q, T};
+
+    #[test]
+    fn first_hold_returns_none() {
+        let mut hold = Hold::new();
+        assert_eq!(hold.swap(T), Swap::Swapped(None));
+        assert_eq!(hold.held(), Some(&T));
+    }
+
+    #[test]
+    fn no_double_hold() {
+        let mut hold = Hold::new();
+        assert_eq!(hold.swap(T), Swap::Swapped(None));
+        assert!(!hold.can_swap());
+        // refused - the incoming piece bounces straight back
+        assert_eq!(hold.swap(I), Swap::Refused(I));
+        assert_eq!(hold.held(), Some(&T));
+        hold.reset();
+        assert_eq!(hold.swap(Q), Swap::Swapped(Some(T)));
+        assert_eq!(hold.held(), Some(&Q));
+    }
+}
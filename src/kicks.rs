@@ -0,0 +1,371 @@
+//! Rotation of pieces on a board, with [SRS](https://tetris.wiki/Super_Rotation_System) wall kicks.
+//!
+//! A piece is a [Grid] the size of the board, containing only the piece's cells.
+//! As in SRS, pieces rotate about the centre of a 3×3 box (4×4 for I), which is found from the
+//! piece's [BoundingBox] and [Orientation],
+//! and if the rotated piece doesn't fit, each offset in the relevant kick table is tried in turn.
+
+use crate::{is_occupied, BlockShape, BoundingBox, Grid};
+use core::mem;
+
+/// The four rotation states of a piece, named as in SRS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Orientation {
+    /// `0`
+    #[default]
+    Spawn,
+    /// `R`, one clockwise turn from spawn
+    Right,
+    /// `2`, two turns from spawn
+    Two,
+    /// `L`, one anticlockwise turn from spawn
+    Left,
+}
+
+impl Orientation {
+    /// The orientation after one clockwise turn.
+    pub fn cw(self) -> Self {
+        match self {
+            Orientation::Spawn => Orientation::Right,
+            Orientation::Right => Orientation::Two,
+            Orientation::Two => Orientation::Left,
+            Orientation::Left => Orientation::Spawn,
+        }
+    }
+
+    /// The orientation of each shape as placed by [tetromino](crate::tetromino).
+    /// ```
+    /// use tetris::{kicks::Orientation, BlockShape};
+    /// // T is placed pointing down
+    /// assert_eq!(Orientation::of_tetromino(BlockShape::T), Orientation::Two);
+    /// ```
+    pub fn of_tetromino(shape: BlockShape) -> Self {
+        match shape {
+            BlockShape::T => Orientation::Two,
+            BlockShape::J => Orientation::Left,
+            BlockShape::L => Orientation::Right,
+            BlockShape::Q | BlockShape::Z | BlockShape::S | BlockShape::I => Orientation::Spawn,
+        }
+    }
+}
+
+/// The square which a piece with `bbox` rotates within, when in `orientation`: its top row, left column, and size.
+///
+/// Within its box, each of J, L, S, T and Z leaves the bottom row empty in [Orientation::Spawn],
+/// the left column empty in [Orientation::Right], and the top row empty in [Orientation::Two].
+/// The I piece lies in the second row, third column, third row, and second column respectively.
+/// The O piece's box is its bounding box, so it doesn't move.
+fn srs_box(bbox: BoundingBox, orientation: Orientation) -> (isize, isize, usize) {
+    let (size, (above, before)) = match (bbox.height, bbox.width) {
+        (2, 2) => (2, (0, 0)),
+        (1, 4) | (4, 1) => (
+            4,
+            match orientation {
+                Orientation::Spawn => (1, 0),
+                Orientation::Right => (0, 2),
+                Orientation::Two => (2, 0),
+                Orientation::Left => (0, 1),
+            },
+        ),
+        _ => (
+            3,
+            match orientation {
+                Orientation::Spawn | Orientation::Left => (0, 0),
+                Orientation::Right => (0, 1),
+                Orientation::Two => (1, 0),
+            },
+        ),
+    };
+    (bbox.top as isize - above, bbox.left as isize - before, size)
+}
+
+/// Kick offsets `(x, y)` for clockwise rotation of J, L, S, T and Z pieces, indexed by the starting [Orientation].
+/// As in the SRS literature, `x` is rightward and `y` is *upward*.
+pub const JLSTZ_CW_KICKS: [[(isize, isize); 5]; 4] = [
+    [(0, 0), (-1, 0), (-1, 1), (0, -2), (-1, -2)], // 0 → R
+    [(0, 0), (1, 0), (1, -1), (0, 2), (1, 2)],     // R → 2
+    [(0, 0), (1, 0), (1, 1), (0, -2), (1, -2)],    // 2 → L
+    [(0, 0), (-1, 0), (-1, -1), (0, 2), (-1, 2)],  // L → 0
+];
+
+/// Kick offsets `(x, y)` for clockwise rotation of the I piece, indexed by the starting [Orientation].
+/// As in the SRS literature, `x` is rightward and `y` is *upward*.
+pub const I_CW_KICKS: [[(isize, isize); 5]; 4] = [
+    [(0, 0), (-2, 0), (1, 0), (-2, -1), (1, 2)], // 0 → R
+    [(0, 0), (-1, 0), (2, 0), (-1, 2), (2, -1)], // R → 2
+    [(0, 0), (2, 0), (-1, 0), (2, 1), (-1, -2)], // 2 → L
+    [(0, 0), (1, 0), (-2, 0), (1, -2), (-2, 1)], // L → 0
+];
+
+impl<const WIDTH: usize, const HEIGHT: usize, CellT> Grid<WIDTH, HEIGHT, CellT>
+where
+    CellT: Default + Clone + PartialEq,
+{
+    /// Rotate the piece in this grid a quarter turn clockwise, about the top-left of its bounding box.
+    ///
+    /// Returns [None] if the rotated piece would fall off the edge of the grid.
+    /// ```
+    /// use tetris::grid;
+    /// assert_eq!(grid![
+    ///     [. . . . ],
+    ///     [. # # # ],
+    ///     [. . # . ],
+    ///     [. . . . ],
+    /// ].rotate_piece_cw(),
+    /// Some(grid![
+    ///     [. . . . ],
+    ///     [. . # . ],
+    ///     [. # # . ],
+    ///     [. . # . ],
+    /// ]));
+    /// ```
+    pub fn rotate_piece_cw(self) -> Option<Self> {
+        let Some(bbox) = self.bounding_box() else {
+            return Some(self);
+        };
+        let about = (bbox.top as isize, bbox.left as isize, bbox.height);
+        self.try_rotate_piece_cw(None, about, (0, 0)).ok()
+    }
+
+    /// Rotate the piece in this grid clockwise, kicking it out of walls and blocks on `board` if necessary.
+    /// The piece is assumed to be in its [Orientation::Spawn] state, at column `col`.
+    ///
+    /// Returns the rotated piece and the column of its leftmost cell, or [None] if no kick fits.
+    /// See [Grid::rotate_cw_kicked_from] for the other orientations.
+    pub fn rotate_cw_kicked(self, board: &Self, col: usize) -> Option<(Self, usize)> {
+        self.rotate_cw_kicked_from(board, col, Orientation::Spawn)
+    }
+
+    /// Rotate the piece in this grid clockwise from `from`, trying each offset in the SRS kick table in turn.
+    /// The I piece (a 1×4 or 4×1 bounding box) uses [I_CW_KICKS], everything else uses [JLSTZ_CW_KICKS].
+    ///
+    /// Returns the first rotated piece which fits on `board`, and the column of its leftmost cell.
+    pub fn rotate_cw_kicked_from(
+        self,
+        board: &Self,
+        col: usize,
+        from: Orientation,
+    ) -> Option<(Self, usize)> {
+        let Some(bbox) = self.bounding_box() else {
+            return Some((self, col));
+        };
+        let kicks = match (bbox.height, bbox.width) {
+            (1, 4) | (4, 1) => &I_CW_KICKS,
+            _ => &JLSTZ_CW_KICKS,
+        };
+        let about = srs_box(bbox, from);
+        let mut piece = self;
+        for &(x, y) in &kicks[from as usize] {
+            // y is upward, rows are downward
+            match piece.try_rotate_piece_cw(Some(board), about, (-y, x)) {
+                Ok(rotated) => {
+                    let col = rotated.bounding_box().map_or(col, |bbox| bbox.left);
+                    return Some((rotated, col));
+                }
+                Err(unrotated) => piece = unrotated,
+            }
+        }
+        None
+    }
+
+    /// Rotate clockwise within the square `about` (its top row, left column, and size),
+    /// and translate by `(d_row, d_col)`, handing `self` back if the result would be out of bounds or collide with `board`.
+    fn try_rotate_piece_cw(
+        mut self,
+        board: Option<&Self>,
+        (top, left, size): (isize, isize, usize),
+        (d_row, d_col): (isize, isize),
+    ) -> Result<Self, Self> {
+        let Some(bbox) = self.bounding_box() else {
+            return Ok(self);
+        };
+        let destination = |row_ix: usize, col_ix: usize| {
+            let to_row = usize::try_from(top + (col_ix as isize - left) + d_row).ok()?;
+            let to_col =
+                usize::try_from(left + (size as isize - 1 - (row_ix as isize - top)) + d_col)
+                    .ok()?;
+            let fits = to_row < HEIGHT
+                && to_col < WIDTH
+                && !board.is_some_and(|board| is_occupied(&board.rows[to_row][to_col]));
+            fits.then_some((to_row, to_col))
+        };
+        let cells = || {
+            (bbox.top..bbox.top + bbox.height).flat_map(move |row_ix| {
+                (bbox.left..bbox.left + bbox.width).map(move |col_ix| (row_ix, col_ix))
+            })
+        };
+        // check every cell before moving any, so that we can hand `self` back intact
+        for (row_ix, col_ix) in cells() {
+            if is_occupied(&self.rows[row_ix][col_ix]) && destination(row_ix, col_ix).is_none() {
+                return Err(self);
+            }
+        }
+        let mut rotated = Self::default();
+        for (row_ix, col_ix) in cells() {
+            if let (true, Some((to_row, to_col))) = (
+                is_occupied(&self.rows[row_ix][col_ix]),
+                destination(row_ix, col_ix),
+            ) {
+                rotated.rows[to_row][to_col] = mem::take(&mut self.rows[row_ix][col_ix]);
+            }
+        }
+        Ok(rotated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid;
+
+    #[test]
+    fn rotate_in_open_space_needs_no_kick() {
+        assert_eq!(
+            grid![
+                [. . # . .],
+                [. # # # .],
+                [. . . . .],
+                [. . . . .],
+            ]
+            .rotate_cw_kicked(&Grid::default(), 1),
+            Some((
+                grid![
+                    [. . # . .],
+                    [. . # # .],
+                    [. . # . .],
+                    [. . . . .],
+                ],
+                2
+            ))
+        )
+    }
+
+    #[test]
+    fn rotate_off_edge_fails_without_kicks() {
+        assert_eq!(grid![[# # #], [. # .]].rotate_piece_cw(), None)
+    }
+
+    #[test]
+    fn i_turns_about_its_centre() {
+        let board = Grid::<4, 4>::default();
+        let i = grid![
+            [. . . .],
+            [# # # #],
+            [. . . .],
+            [. . . .],
+        ];
+        let (right, col) = i.rotate_cw_kicked(&board, 0).unwrap();
+        assert_eq!(
+            right,
+            grid![
+                [. . # .],
+                [. . # .],
+                [. . # .],
+                [. . # .],
+            ]
+        );
+        assert_eq!(col, 2);
+        let (two, _) = right
+            .rotate_cw_kicked_from(&board, col, Orientation::Right)
+            .unwrap();
+        assert_eq!(
+            two,
+            grid![
+                [. . . .],
+                [. . . .],
+                [# # # #],
+                [. . . .],
+            ]
+        );
+    }
+
+    #[test]
+    fn placed_tetrominoes_turn_like_srs() {
+        // each shape, as placed, turned clockwise into its spawn state
+        let board = Grid::<4, 4>::default();
+        let spawn_of = |shape| {
+            let mut piece = crate::tetromino::<4, 4>(shape) >> 1;
+            let mut orientation = Orientation::of_tetromino(shape);
+            while orientation != Orientation::Spawn {
+                (piece, _) = piece.rotate_cw_kicked_from(&board, 1, orientation).unwrap();
+                orientation = orientation.cw();
+            }
+            piece
+        };
+        // T is kicked down, away from the top of the board, on its first turn
+        assert_eq!(
+            spawn_of(BlockShape::T),
+            grid![
+                [. . . .],
+                [. . # .],
+                [. # # #],
+                [. . . .],
+            ]
+        );
+        assert_eq!(
+            spawn_of(BlockShape::J),
+            grid![
+                [. # . .],
+                [. # # #],
+                [. . . .],
+                [. . . .],
+            ]
+        );
+        assert_eq!(
+            spawn_of(BlockShape::L),
+            grid![
+                [. . # .],
+                [# # # .],
+                [. . . .],
+                [. . . .],
+            ]
+        );
+    }
+
+    // the T-spin triple from the fifth 0 → R kick
+    #[test]
+    fn t_spin_triple_kick() {
+        let board = grid![
+            [. . . . .],
+            [# . . . .],
+            [. . . . .],
+            [. # # # #],
+            [. . # # #],
+            [. # # # #],
+        ];
+        let t = grid![
+            [. . . . .],
+            [. # . . .],
+            [# # # . .],
+            [. . . . .],
+            [. . . . .],
+            [. . . . .],
+        ];
+        // (0, 0) and (0, -2) hit the overhang at the top of the slot, (-1, 0) and (-1, 1) hit the roof,
+        // and (-1, -2) slots the T into the hole
+        let (kicked, col) = t.rotate_cw_kicked(&board, 0).unwrap();
+        assert_eq!(
+            kicked,
+            grid![
+                [. . . . .],
+                [. . . . .],
+                [. . . . .],
+                [# . . . .],
+                [# # . . .],
+                [# . . . .],
+            ]
+        );
+        assert_eq!(col, 0);
+        assert_eq!(
+            (board & kicked).unwrap().with_solid_rows_cleared(),
+            grid![
+                [. . . . .],
+                [. . . . .],
+                [. . . . .],
+                [. . . . .],
+                [# . . . .],
+                [. . . . .],
+            ]
+        );
+    }
+}
@@ -6,6 +6,8 @@
 //!
 //! [Grid]s may be scrolled as follows, with tetris semantics:
 //! - [std::ops::Shr](struct.Grid.html#impl-Shr<usize>-for-Grid<WIDTH%2C%20HEIGHT%2C%20CellT>) will discard the rightmost column, and create a new empty leftmost column.
+//!   [std::ops::Shl](struct.Grid.html#impl-Shl<usize>-for-Grid<WIDTH%2C%20HEIGHT%2C%20CellT>) is the mirror image.
+//!   [Grid::checked_shr] and [Grid::checked_shl] will fail rather than discard any blocks.
 //! - [Grid::try_bump_down] will discard the lowermost column, and create a new empty top column.
 //!   It will fail if the lowermost column contains any blocks.
 //!
@@ -13,12 +15,50 @@
 //! - [std::ops::BitAnd](struct.Grid.html#impl-BitAnd%3CGrid%3CWIDTH%2C%20HEIGHT%2C%20CellT%3E%3E-for-Grid%3CWIDTH%2C%20HEIGHT%2C%20CellT%3E) will try and place one grid atop another, failing if any cell is occupied in both grids.
 //! - [Grid::drop] will place a grid on another, applying gravity until the other hits a block or the floor.
 //! - [Grid::with_solid_rows_cleared] will clear filled rows, and scroll the rest of the board down to fill.
+//! - [Grid::rotate_cw_kicked] will rotate a piece, using [SRS](kicks) wall kicks if it's blocked.
+//!
+//! Without the default `std` feature, this crate is `no_std`, and doesn't allocate.
+//! Methods returning a [Vec], and text parsing, require `std`.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(feature = "std")]
+mod ascii;
+pub mod bitgrid;
+#[cfg(feature = "std")]
+mod bits;
+#[cfg(feature = "std")]
+pub mod bot;
+#[cfg(feature = "colors")]
+pub mod colors;
+#[cfg(feature = "fumen")]
+pub mod fumen;
+pub mod hold;
+pub mod kicks;
+pub mod lock;
+#[cfg(feature = "std")]
+pub mod play;
+#[cfg(feature = "std")]
+pub mod queue;
+#[cfg(feature = "rand")]
+pub mod random;
+pub mod score;
+pub mod tracked;
+pub mod tspin;
+
+#[cfg(feature = "std")]
+pub use ascii::ParseGridError;
+#[cfg(feature = "std")]
+pub use bits::FromBitsError;
 
 use array_macro::array;
-use std::{
+use core::{
     fmt, mem,
     ops::{self, BitAnd},
 };
+use kicks::Orientation;
+use serde::Deserialize;
+use strum::EnumString;
 
 /// A generic matrix of cells.
 /// See [module documentation](index.html) for more.
@@ -27,7 +67,7 @@ use std::{
 // choice: row-wise, because we'll be searching and clearing rows
 // choice: generic CellT, not e.g bitvec because a likely product extension is
 //         coloring individual blocks etc
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Grid<const WIDTH: usize, const HEIGHT: usize, CellT = CellState> {
     pub rows: [[CellT; WIDTH]; HEIGHT],
 }
@@ -36,9 +76,379 @@ impl<const WIDTH: usize, const HEIGHT: usize, CellT> Grid<WIDTH, HEIGHT, CellT>
 where
     CellT: Default + Clone,
 {
-    fn empty_row() -> [CellT; WIDTH] {
+    /// A grid with every cell empty. The same as [Grid::default].
+    /// ```
+    /// use tetris::Grid;
+    /// assert_eq!(Grid::<4, 4>::empty(), Grid::default());
+    /// ```
+    pub fn empty() -> Self {
+        Self::default()
+    }
+
+    /// A row with every cell empty.
+    pub fn blank_row() -> [CellT; WIDTH] {
         array![CellT::default(); WIDTH]
     }
+
+    /// Move every cell down by `d_row` and right by `d_col`, either of which may be negative.
+    /// Cells pushed off any edge are discarded, and the cells left behind are empty.
+    /// ```
+    /// use tetris::grid;
+    /// let grid = grid![
+    ///     [. . .],
+    ///     [. # .],
+    ///     [. # #],
+    /// ];
+    /// assert_eq!(
+    ///     grid.shift_by(-1, -1),
+    ///     grid![
+    ///         [# . .],
+    ///         [# # .],
+    ///         [. . .],
+    ///     ]
+    /// );
+    /// ```
+    pub fn shift_by(mut self, d_row: isize, d_col: isize) -> Self {
+        let mut shifted = Self::default();
+        for (row_ix, row) in self.rows.iter_mut().enumerate() {
+            for (col_ix, cell) in row.iter_mut().enumerate() {
+                if let (Some(to_row), Some(to_col)) = (
+                    row_ix.checked_add_signed(d_row),
+                    col_ix.checked_add_signed(d_col),
+                ) {
+                    if to_row < HEIGHT && to_col < WIDTH {
+                        shifted.rows[to_row][to_col] = mem::take(cell)
+                    }
+                }
+            }
+        }
+        shifted
+    }
+}
+
+impl<const WIDTH: usize, const HEIGHT: usize, CellT> Grid<WIDTH, HEIGHT, CellT> {
+    /// A grid with every cell set to `value`.
+    /// Unlike [Grid::default], this doesn't need `CellT: Default`,
+    /// and nor do the other methods which only rearrange cells, like [Grid::map] and [Grid::rotate_cw].
+    /// ```
+    /// use tetris::Grid;
+    /// assert_eq!(Grid::<3, 1, u8>::filled_with(7).rows, [[7, 7, 7]]);
+    /// ```
+    pub fn filled_with(value: CellT) -> Self
+    where
+        CellT: Clone,
+    {
+        Self {
+            rows: array![array![value.clone(); WIDTH]; HEIGHT],
+        }
+    }
+
+    /// Iterate over the rows, top to bottom.
+    pub fn iter_rows(&self) -> impl Iterator<Item = &[CellT; WIDTH]> {
+        self.rows.iter()
+    }
+
+    /// Iterate over the columns, left to right. Each column is listed top to bottom.
+    /// ```
+    /// use tetris::{grid, is_occupied};
+    /// let grid = grid![
+    ///     [. # .],
+    ///     [# # .],
+    /// ];
+    /// let column_counts = grid
+    ///     .iter_cols()
+    ///     .map(|col| col.into_iter().filter(|cell| is_occupied(*cell)).count())
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(column_counts, [1, 2, 0]);
+    /// ```
+    pub fn iter_cols(&self) -> impl Iterator<Item = [&CellT; HEIGHT]> {
+        (0..WIDTH).map(move |col_ix| array![row_ix => &self.rows[row_ix][col_ix]; HEIGHT])
+    }
+
+    /// Iterate over every cell with its `(row, column)`, row by row.
+    /// ```
+    /// use tetris::{grid, is_occupied};
+    /// let grid = grid![
+    ///     [. # .],
+    ///     [# . .],
+    /// ];
+    /// let occupied = grid
+    ///     .cells()
+    ///     .filter(|(_, cell)| is_occupied(*cell))
+    ///     .map(|(coords, _)| coords)
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(occupied, [(0, 1), (1, 0)]);
+    /// ```
+    pub fn cells(&self) -> impl Iterator<Item = ((usize, usize), &CellT)> {
+        self.rows.iter().enumerate().flat_map(|(row_ix, row)| {
+            row.iter()
+                .enumerate()
+                .map(move |(col_ix, cell)| ((row_ix, col_ix), cell))
+        })
+    }
+
+    /// The in-bounds cells above, below, left and right of `(row_ix, col_ix)`, as `(row, column)`.
+    /// ```
+    /// use tetris::{CellState, Grid};
+    /// let grid = Grid::<3, 3, CellState>::default();
+    /// assert_eq!(grid.neighbors(0, 0).collect::<Vec<_>>(), [(1, 0), (0, 1)]);
+    /// assert_eq!(grid.neighbors(0, 1).count(), 3);
+    /// assert_eq!(grid.neighbors(1, 1).count(), 4);
+    /// ```
+    pub fn neighbors(&self, row_ix: usize, col_ix: usize) -> impl Iterator<Item = (usize, usize)> {
+        [(-1, 0), (1, 0), (0, -1), (0, 1)]
+            .into_iter()
+            .filter_map(move |(d_row, d_col)| {
+                let row_ix = row_ix.checked_add_signed(d_row).filter(|&ix| ix < HEIGHT)?;
+                let col_ix = col_ix.checked_add_signed(d_col).filter(|&ix| ix < WIDTH)?;
+                Some((row_ix, col_ix))
+            })
+    }
+
+    /// Transform every cell with `f`, row by row.
+    /// ```
+    /// use tetris::{grid, is_occupied, Grid};
+    /// assert_eq!(grid![
+    ///     [# . ],
+    ///     [. # ],
+    /// ].map(|cell| is_occupied(&cell)),
+    /// Grid {
+    ///     rows: [
+    ///         [true, false],
+    ///         [false, true],
+    ///     ]
+    /// });
+    /// ```
+    pub fn map<U, F>(self, mut f: F) -> Grid<WIDTH, HEIGHT, U>
+    where
+        F: FnMut(CellT) -> U,
+    {
+        Grid {
+            rows: self.rows.map(|row| row.map(&mut f)),
+        }
+    }
+
+    /// Combine each cell with the cell in the same position in `other`, using `f`.
+    /// The const generics guarantee that both grids have the same dimensions.
+    pub fn zip_with<U, V, F>(
+        self,
+        other: Grid<WIDTH, HEIGHT, U>,
+        mut f: F,
+    ) -> Grid<WIDTH, HEIGHT, V>
+    where
+        F: FnMut(CellT, U) -> V,
+    {
+        let mut other_rows = other.rows.into_iter();
+        Grid {
+            rows: self.rows.map(|row| {
+                let mut other_cells = other_rows.next().expect("same HEIGHT").into_iter();
+                row.map(|cell| f(cell, other_cells.next().expect("same WIDTH")))
+            }),
+        }
+    }
+
+    /// Call `f` with `(row_ix, col_ix, cell)` for every cell, row by row.
+    /// ```
+    /// use tetris::{grid, CellState};
+    /// let mut grid = grid![
+    ///     [# .],
+    ///     [. .],
+    /// ];
+    /// grid.for_each_cell_mut(|row_ix, col_ix, cell| {
+    ///     if row_ix == col_ix {
+    ///         *cell = CellState::Occupied
+    ///     }
+    /// });
+    /// assert_eq!(grid, grid![
+    ///     [# .],
+    ///     [. #],
+    /// ]);
+    /// ```
+    pub fn for_each_cell_mut(&mut self, mut f: impl FnMut(usize, usize, &mut CellT)) {
+        for (row_ix, row) in self.rows.iter_mut().enumerate() {
+            for (col_ix, cell) in row.iter_mut().enumerate() {
+                f(row_ix, col_ix, cell)
+            }
+        }
+    }
+
+    /// A grid with `with` at each `(row, column)` in `coords`, and every other cell empty.
+    /// ```
+    /// use tetris::{grid, CellState, Grid};
+    /// assert_eq!(
+    ///     Grid::from_coords([(0, 0), (0, 1), (1, 0), (1, 1)], CellState::Occupied),
+    ///     grid![
+    ///         [# # .],
+    ///         [# # .],
+    ///         [. . .],
+    ///     ]
+    /// );
+    /// ```
+    /// # Panics
+    /// - If any of the coords are out of bounds.
+    pub fn from_coords(coords: impl IntoIterator<Item = (usize, usize)>, with: CellT) -> Self
+    where
+        CellT: Default + Clone,
+    {
+        let mut grid = Self::default();
+        for (row_ix, col_ix) in coords {
+            grid.rows[row_ix][col_ix] = with.clone();
+        }
+        grid
+    }
+
+    /// Set every cell in the rectangle with its top-left corner at `(top, left)` to `with`.
+    /// # Panics
+    /// - If the rectangle extends past the edge of the grid.
+    pub fn fill_rect(&mut self, top: usize, left: usize, height: usize, width: usize, with: CellT)
+    where
+        CellT: Clone,
+    {
+        assert!(
+            top + height <= HEIGHT && left + width <= WIDTH,
+            "{height}x{width} rectangle at ({top}, {left}) is out of bounds"
+        );
+        for row in &mut self.rows[top..top + height] {
+            row[left..left + width].fill(with.clone())
+        }
+    }
+
+    /// A copy with every cell outside the rectangle with its top-left corner at `(top, left)` emptied.
+    /// ```
+    /// use tetris::{grid, CellState, Grid};
+    /// assert_eq!(
+    ///     Grid::<4, 3, CellState>::full().masked_to(1, 1, 2, 2),
+    ///     grid![
+    ///         [. . . .],
+    ///         [. # # .],
+    ///         [. # # .],
+    ///     ]
+    /// );
+    /// ```
+    /// # Panics
+    /// - If the rectangle extends past the edge of the grid.
+    pub fn masked_to(&self, top: usize, left: usize, height: usize, width: usize) -> Self
+    where
+        CellT: Default + Clone,
+    {
+        assert!(
+            top + height <= HEIGHT && left + width <= WIDTH,
+            "{height}x{width} rectangle at ({top}, {left}) is out of bounds"
+        );
+        let mut masked = Self::default();
+        for row_ix in top..top + height {
+            masked.rows[row_ix][left..left + width]
+                .clone_from_slice(&self.rows[row_ix][left..left + width])
+        }
+        masked
+    }
+
+    /// Cyclically shift each row `by` cells to the right, wrapping cells off the right edge around to the left.
+    /// Unlike [std::ops::Shr](struct.Grid.html#impl-Shr<usize>-for-Grid<WIDTH%2C%20HEIGHT%2C%20CellT>), no cells are lost.
+    /// ```
+    /// use tetris::grid;
+    /// assert_eq!(grid![
+    ///     [. . #],
+    ///     [. # .],
+    /// ].rotate_rows(1),
+    /// grid![
+    ///     [# . .], // ↰ wrapped around
+    ///     [. . #],
+    /// ]);
+    /// ```
+    pub fn rotate_rows(mut self, by: usize) -> Self {
+        let by = by.checked_rem(WIDTH).unwrap_or(0);
+        for row in &mut self.rows {
+            row.rotate_right(by)
+        }
+        self
+    }
+
+    /// Cyclically shift the rows `by` rows up, wrapping rows off the top around to the bottom.
+    pub fn rotate_board_up(mut self, by: usize) -> Self {
+        self.rows.rotate_left(by.checked_rem(HEIGHT).unwrap_or(0));
+        self
+    }
+
+    /// Cyclically shift the rows `by` rows down, wrapping rows off the bottom around to the top.
+    /// Unlike [Grid::try_bump_down], no cells are lost.
+    pub fn rotate_board_down(mut self, by: usize) -> Self {
+        self.rows.rotate_right(by.checked_rem(HEIGHT).unwrap_or(0));
+        self
+    }
+
+    /// Rotate the whole grid a quarter turn clockwise, swapping its dimensions.
+    /// ```
+    /// use tetris::{grid, CellState, Grid};
+    /// let rotated: Grid<2, 3, CellState> = grid![
+    ///     [# # #],
+    ///     [. . #],
+    /// ].rotate_cw();
+    /// assert_eq!(rotated, grid![
+    ///     [. #],
+    ///     [. #],
+    ///     [# #],
+    /// ]);
+    /// ```
+    pub fn rotate_cw(self) -> Grid<HEIGHT, WIDTH, CellT> {
+        let mut cells = self.map(Some);
+        Grid {
+            rows: array![row_ix => array![col_ix => take_moved(&mut cells.rows[HEIGHT - 1 - col_ix][row_ix]); HEIGHT]; WIDTH],
+        }
+    }
+
+    /// Swap rows and columns, so that `rows[r][c]` becomes `rows[c][r]`.
+    /// `grid.transpose().transpose()` is always `grid`.
+    /// ```
+    /// use tetris::{grid, CellState, Grid};
+    /// let grid: Grid<3, 2, CellState> = grid![
+    ///     [# # #],
+    ///     [. . #],
+    /// ];
+    /// let transposed: Grid<2, 3, CellState> = grid.transpose();
+    /// assert_eq!(transposed, grid![
+    ///     [# .],
+    ///     [# .],
+    ///     [# #],
+    /// ]);
+    /// assert_eq!(transposed.transpose(), grid);
+    /// ```
+    pub fn transpose(self) -> Grid<HEIGHT, WIDTH, CellT> {
+        let mut cells = self.map(Some);
+        Grid {
+            rows: array![row_ix => array![col_ix => take_moved(&mut cells.rows[col_ix][row_ix]); HEIGHT]; WIDTH],
+        }
+    }
+
+    /// Reflect across the anti-diagonal (top-right to bottom-left),
+    /// so that `rows[r][c]` becomes `rows[WIDTH - 1 - c][HEIGHT - 1 - r]`.
+    /// This is [Grid::rotate_cw] followed by turning the grid upside down.
+    /// ```
+    /// use tetris::{grid, CellState, Grid};
+    /// let grid: Grid<3, 2, CellState> = grid![
+    ///     [# # .],
+    ///     [. . #],
+    /// ];
+    /// let mut composed = grid.rotate_cw();
+    /// composed.rows.reverse();
+    /// assert_eq!(grid.anti_transpose(), composed);
+    /// assert_eq!(grid.anti_transpose(), grid![
+    ///     [# .],
+    ///     [. #],
+    ///     [. #],
+    /// ]);
+    /// ```
+    pub fn anti_transpose(self) -> Grid<HEIGHT, WIDTH, CellT> {
+        let mut cells = self.map(Some);
+        Grid {
+            rows: array![row_ix => array![col_ix => take_moved(&mut cells.rows[HEIGHT - 1 - col_ix][WIDTH - 1 - row_ix]); HEIGHT]; WIDTH],
+        }
+    }
+}
+
+/// Move a cell out of a grid of [Option]s, where each cell is moved exactly once.
+fn take_moved<T>(cell: &mut Option<T>) -> T {
+    cell.take().expect("each cell is only moved once")
 }
 
 impl<const WIDTH: usize, const HEIGHT: usize, CellT> Default for Grid<WIDTH, HEIGHT, CellT>
@@ -47,19 +457,173 @@ where
 {
     fn default() -> Self {
         Self {
-            rows: array![Self::empty_row(); HEIGHT],
+            rows: array![Self::blank_row(); HEIGHT],
+        }
+    }
+}
+
+/// Draw the grid with `.` for empty cells and `#` for occupied cells, one line per row.
+/// The output can be read back with [Grid::from_ascii_art].
+/// ```
+/// use tetris::grid;
+/// let grid = grid![
+///     [. # .],
+///     [# # #],
+/// ];
+/// assert_eq!(grid.to_string(), ".#.\n###\n");
+/// ```
+impl<const WIDTH: usize, const HEIGHT: usize, CellT> fmt::Display for Grid<WIDTH, HEIGHT, CellT>
+where
+    CellT: Default + PartialEq,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in &self.rows {
+            for cell in row {
+                f.write_str(if is_occupied(cell) { "#" } else { "." })?
+            }
+            writeln!(f)?
         }
+        Ok(())
     }
 }
 
 /// The first colliding indices when trying to combine [Grid]s with [std::ops::BitAnd](struct.Grid.html#impl-BitAnd<Grid<WIDTH%2C%20HEIGHT%2C%20CellT>>-for-Grid<WIDTH%2C%20HEIGHT%2C%20CellT>).
-#[derive(Debug, thiserror::Error, PartialEq, Eq, Clone, Copy)]
-#[error("would clobber non-default cell at row {row_ix}, column {col_ix} (this is the first clobber, there may be more)")]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "std", derive(thiserror::Error), error("would clobber non-default cell at row {row_ix}, column {col_ix} (this is the first clobber, there may be more)"))]
 pub struct WouldClobber {
     pub row_ix: usize,
     pub col_ix: usize,
 }
 
+// thiserror needs std
+#[cfg(not(feature = "std"))]
+impl fmt::Display for WouldClobber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { row_ix, col_ix } = self;
+        write!(f, "would clobber non-default cell at row {row_ix}, column {col_ix} (this is the first clobber, there may be more)")
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl core::error::Error for WouldClobber {}
+
+/// Why [Grid::try_shift_down_checked] failed.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(
+    feature = "std",
+    derive(thiserror::Error),
+    error("couldn't shift down on step {at_step}: row {blocking_row} would fall off the bottom")
+)]
+pub struct ShiftBlocked {
+    /// The number of bumps which succeeded before this one.
+    pub at_step: usize,
+    /// The index of the occupied row in the original grid.
+    pub blocking_row: usize,
+}
+
+#[cfg(not(feature = "std"))]
+impl fmt::Display for ShiftBlocked {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            at_step,
+            blocking_row,
+        } = self;
+        write!(
+            f,
+            "couldn't shift down on step {at_step}: row {blocking_row} would fall off the bottom"
+        )
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl core::error::Error for ShiftBlocked {}
+
+/// Why [Grid::play] failed.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "std", derive(thiserror::Error))]
+pub enum PlayError {
+    #[cfg_attr(
+        feature = "std",
+        error("{shape:?}{column} would hang off the right edge: {shape:?} is {shape_width} wide, and the grid is {grid_width} wide")
+    )]
+    HangsOffEdge {
+        shape: BlockShape,
+        column: usize,
+        shape_width: usize,
+        grid_width: usize,
+    },
+    #[cfg_attr(
+        feature = "std",
+        error("game over: spawn area blocked by {shape:?}{column}")
+    )]
+    SpawnBlocked { shape: BlockShape, column: usize },
+    #[cfg_attr(
+        feature = "std",
+        error("couldn't turn {shape:?}{column} clockwise {rotation} times: no kick fits")
+    )]
+    RotationBlocked {
+        shape: BlockShape,
+        column: usize,
+        rotation: u8,
+    },
+}
+
+#[cfg(not(feature = "std"))]
+impl fmt::Display for PlayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::HangsOffEdge {
+                shape,
+                column,
+                shape_width,
+                grid_width,
+            } => write!(f, "{shape:?}{column} would hang off the right edge: {shape:?} is {shape_width} wide, and the grid is {grid_width} wide"),
+            Self::SpawnBlocked { shape, column } => {
+                write!(f, "game over: spawn area blocked by {shape:?}{column}")
+            }
+            Self::RotationBlocked {
+                shape,
+                column,
+                rotation,
+            } => write!(
+                f,
+                "couldn't turn {shape:?}{column} clockwise {rotation} times: no kick fits"
+            ),
+        }
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl core::error::Error for PlayError {}
+
+/// Why [Grid::drop_checked] failed.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "std", derive(thiserror::Error))]
+pub enum DropError {
+    /// The stack has reached the top of the grid.
+    #[cfg_attr(feature = "std", error("the stack has reached the top of the grid"))]
+    Overflow,
+    /// The piece collides with the stack where it spawned.
+    #[cfg_attr(
+        feature = "std",
+        error("the piece collides with the stack where it spawned")
+    )]
+    SpawnBlocked,
+}
+
+#[cfg(not(feature = "std"))]
+impl fmt::Display for DropError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Overflow => "the stack has reached the top of the grid",
+            Self::SpawnBlocked => "the piece collides with the stack where it spawned",
+        })
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl core::error::Error for DropError {}
+
 pub fn is_empty<T: Default + PartialEq>(t: &T) -> bool {
     t == &T::default()
 }
@@ -140,6 +704,43 @@ where
     }
 }
 
+/// Union `rhs` into `self`. Unlike [std::ops::BitAnd](struct.Grid.html#impl-BitAnd<Grid<WIDTH%2C%20HEIGHT%2C%20CellT>>-for-Grid<WIDTH%2C%20HEIGHT%2C%20CellT>),
+/// this never fails: where both grids are occupied, `self`'s cell is kept.
+/// See [Grid::and_assign] for a fallible version.
+/// ```
+/// use tetris::grid;
+/// let mut board = grid![
+///     [. .],
+///     [# .],
+/// ];
+/// board |= grid![
+///     [. .],
+///     [# #],
+/// ];
+/// assert_eq!(board, grid![
+///     [. .],
+///     [# #],
+/// ]);
+/// ```
+impl<const WIDTH: usize, const HEIGHT: usize, CellT> ops::BitOrAssign<Self>
+    for Grid<WIDTH, HEIGHT, CellT>
+where
+    CellT: Default + PartialEq,
+{
+    fn bitor_assign(&mut self, mut rhs: Self) {
+        for (lhs, rhs) in self
+            .rows
+            .iter_mut()
+            .flatten()
+            .zip(rhs.rows.iter_mut().flatten())
+        {
+            if is_empty(lhs) {
+                mem::swap(lhs, rhs)
+            }
+        }
+    }
+}
+
 impl<const WIDTH: usize, const HEIGHT: usize, CellT> ops::Shr<usize> for Grid<WIDTH, HEIGHT, CellT>
 where
     CellT: Default,
@@ -187,6 +788,40 @@ where
     }
 }
 
+impl<const WIDTH: usize, const HEIGHT: usize, CellT> ops::Shl<usize> for Grid<WIDTH, HEIGHT, CellT>
+where
+    CellT: Default,
+{
+    type Output = Self;
+
+    /// Push leftmost column off the edge, filling a new rightmost column with the default.
+    /// ```
+    /// use tetris::grid;
+    /// assert_eq!(
+    /// grid![
+    ///     [. # # ],
+    ///     [. . # ],
+    /// ] << 1,
+    /// grid![
+    ///     [# # . ], // ←
+    ///     [. # . ], // ←
+    /// ]);
+    /// ```
+    fn shl(mut self, rhs: usize) -> Self::Output {
+        for _ in 0..rhs {
+            for row in self.rows.iter_mut() {
+                if let Some(leftmost_cell) = row.first_mut() {
+                    *leftmost_cell = Default::default()
+                }
+                if WIDTH > 1 {
+                    row.rotate_left(1)
+                }
+            }
+        }
+        self
+    }
+}
+
 impl<const WIDTH: usize, const HEIGHT: usize, CellT> Grid<WIDTH, HEIGHT, CellT>
 where
     CellT: Default + Clone + PartialEq,
@@ -217,8 +852,8 @@ where
     ///
     /// ```
     pub fn try_bump_down(mut self) -> Option<Self> {
-        match self.rows.last() {
-            Some(last_row) if last_row.iter().all(is_empty) => {
+        match HEIGHT.checked_sub(1) {
+            Some(last_row_ix) if self.row_is_empty(last_row_ix) => {
                 self.rows.rotate_right(1);
                 Some(self)
             }
@@ -235,6 +870,83 @@ where
         Some(self)
     }
 
+    /// As [Grid::try_shift_down], reporting which row would have fallen off.
+    /// ```
+    /// use tetris::{grid, ShiftBlocked};
+    /// assert_eq!(
+    ///     grid![
+    ///         [#],
+    ///         [.],
+    ///         [.],
+    ///     ]
+    ///     .try_shift_down_checked(3),
+    ///     Err(ShiftBlocked {
+    ///         at_step: 2,
+    ///         blocking_row: 0
+    ///     })
+    /// );
+    /// ```
+    pub fn try_shift_down_checked(mut self, by: usize) -> Result<Self, ShiftBlocked> {
+        for at_step in 0..by {
+            self = match self.try_bump_down() {
+                Some(bumped) => bumped,
+                None => {
+                    return Err(ShiftBlocked {
+                        at_step,
+                        blocking_row: HEIGHT - 1 - at_step,
+                    })
+                }
+            }
+        }
+        Ok(self)
+    }
+
+    /// As [Grid::shift_by], but returns [None] rather than pushing any occupied cells off an edge.
+    /// ```
+    /// use tetris::grid;
+    /// let piece = grid![
+    ///     [. # .],
+    ///     [# # #],
+    /// ];
+    /// assert_eq!(piece.checked_shift_by(-1, 0), None);
+    /// assert_eq!(piece.checked_shift_by(0, 0), Some(piece));
+    /// ```
+    pub fn checked_shift_by(self, d_row: isize, d_col: isize) -> Option<Self> {
+        let fits =
+            self.cells()
+                .filter(|(_, cell)| is_occupied(*cell))
+                .all(|((row_ix, col_ix), _)| {
+                    row_ix
+                        .checked_add_signed(d_row)
+                        .is_some_and(|to_row| to_row < HEIGHT)
+                        && col_ix
+                            .checked_add_signed(d_col)
+                            .is_some_and(|to_col| to_col < WIDTH)
+                });
+        fits.then(|| self.shift_by(d_row, d_col))
+    }
+
+    /// Whether `rhs` can be placed on the grid where it is, without any movement.
+    /// If this is false, [Grid::drop] will fail.
+    /// ```
+    /// use tetris::grid;
+    /// let board = grid![
+    ///     [. . ],
+    ///     [# . ],
+    /// ];
+    /// assert!(board.can_place(&grid![
+    ///     [# # ],
+    ///     [. . ],
+    /// ]));
+    /// assert!(!board.can_place(&grid![
+    ///     [# . ],
+    ///     [# . ], // ← collides
+    /// ]));
+    /// ```
+    pub fn can_place(&self, rhs: &Self) -> bool {
+        self.clone().bitand(rhs.clone()).is_ok()
+    }
+
     /// Place `rhs` on the grid, and move it down until:
     /// - it hits another block
     /// - it hits the bottom of the grid
@@ -279,268 +991,2610 @@ where
         Some(furthest)
     }
 
-    /// Clear full rows by shifting taller rows down
+    /// Like [Grid::drop], but spelling out whether `rhs` could be placed at all.
     /// ```
-    /// use tetris::grid;
-    /// assert_eq!(grid![
-    ///     [. . . . . . . . . .],
-    ///     [. . # # . . . . . .],
-    ///     [. . # # . . . . . .],
-    ///     [# # # # # # # # # #], // ← will be removed
-    ///     [# # . . # # # # # #],
-    /// ].with_solid_rows_cleared(),
-    /// grid![
-    ///     [. . . . . . . . . .], // ↳ fresh new row
-    ///     [. . . . . . . . . .],
-    ///     [. . # # . . . . . .], // ↓
-    ///     [. . # # . . . . . .], // ↓
-    ///     [# # . . # # # # # #],
-    /// ]
-    /// )
+    /// use tetris::{grid, DropOutcome};
+    /// assert_eq!(
+    ///     grid!([.], [.]).try_drop(grid!([#], [.])),
+    ///     DropOutcome::Landed(grid!([.], [#])),
+    /// );
     /// ```
-    pub fn with_solid_rows_cleared(mut self) -> Self {
-        // outer loop is necessary because inner won't check the shifted row
-        // could also do a mark and sweep
-        while self.rows.iter().any(|row| row.iter().all(is_occupied)) {
-            for row_ix in (0..HEIGHT).rev() {
-                if self.rows[row_ix].iter().all(is_occupied) {
-                    self.rows[row_ix] = Self::empty_row();
-                    self.rows[..=row_ix].rotate_right(1);
-                }
-            }
+    pub fn try_drop(self, rhs: Self) -> DropOutcome<Self> {
+        match self.drop(rhs) {
+            Some(landed) => DropOutcome::Landed(landed),
+            None => DropOutcome::Blocked,
         }
-        self
     }
-}
 
-#[derive(Clone, Copy, PartialEq, Eq, Default)]
-pub enum CellState {
-    // The reason we do a song and dance with `Default` above is because
-    // putting information in `Occupied` is now trivial - a likely extension for
-    // the business (e.g adding colours)
-    Occupied,
-    #[default]
-    Unoccupied,
-}
+    /// Like [Grid::drop], but explaining why `rhs` couldn't be placed.
+    /// If `rhs` collides with a column which is stacked all the way to the top row, that's a [DropError::Overflow].
+    pub fn drop_checked(self, rhs: Self) -> Result<Self, DropError> {
+        let overflowed = self.cells().any(|((row_ix, col_ix), cell)| {
+            is_occupied(cell)
+                && is_occupied(&rhs.rows[row_ix][col_ix])
+                && (0..row_ix).all(|above_ix| is_occupied(&self.rows[above_ix][col_ix]))
+        });
+        match self.drop(rhs) {
+            Some(landed) => Ok(landed),
+            None if overflowed => Err(DropError::Overflow),
+            None => Err(DropError::SpawnBlocked),
+        }
+    }
 
-impl fmt::Debug for CellState {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Self::Occupied => write!(f, "#"),
-            Self::Unoccupied => write!(f, "."),
+    /// Where `rhs` would come to rest if [Grid::drop]ped, without combining it with the grid.
+    /// Returns [None] if `rhs` can't be placed.
+    /// ```
+    /// use tetris::grid;
+    /// assert_eq!(
+    ///     grid![
+    ///         [. .],
+    ///         [. .],
+    ///         [# .],
+    ///     ]
+    ///     .ghost(grid![
+    ///         [# #],
+    ///         [. .],
+    ///         [. .],
+    ///     ]),
+    ///     Some(grid![
+    ///         [. .],
+    ///         [# #],
+    ///         [. .],
+    ///     ])
+    /// );
+    /// ```
+    pub fn ghost(&self, rhs: Self) -> Option<Self> {
+        let rows_fallen = self.rows_to_fall(&rhs)?;
+        rhs.try_shift_down(rows_fallen)
+    }
+
+    /// The row where the top of `shape` would come to rest if [Grid::drop]ped, e.g for drawing guide lines.
+    /// Cheaper than [Grid::ghost], as the shape isn't moved.
+    /// Returns [None] if `shape` is empty or can't be placed.
+    pub fn drop_row_for_column(&self, shape: &Self) -> Option<usize> {
+        let top = shape.bounding_box()?.top;
+        Some(top + self.rows_to_fall(shape)?)
+    }
+
+    /// [Grid::drop] `rhs`, also returning where it landed, and how far it fell.
+    /// Returns [None] if `rhs` can't be placed.
+    pub fn hard_drop(self, rhs: Self) -> Option<HardDropResult<Self>> {
+        let rows_fallen = self.rows_to_fall(&rhs)?;
+        let landed_piece = rhs.try_shift_down(rows_fallen)?;
+        let board = self.bitand(landed_piece.clone()).ok()?;
+        Some(HardDropResult {
+            board,
+            landed_piece,
+            rows_fallen,
+        })
+    }
+
+    /// The cells of `landed_piece` (e.g from [Grid::ghost]) which rest directly on the floor or an occupied cell of this grid,
+    /// as `(row, column)`, top to bottom.
+    #[cfg(feature = "std")]
+    pub fn landing_contacts(&self, landed_piece: &Self) -> Vec<(usize, usize)> {
+        landed_piece
+            .cells()
+            .filter(|&((row_ix, col_ix), cell)| {
+                let supported = match self.rows.get(row_ix + 1) {
+                    Some(below) => is_occupied(&below[col_ix]),
+                    None => true, // the floor
+                };
+                is_occupied(cell) && supported
+            })
+            .map(|(coords, _)| coords)
+            .collect()
+    }
+
+    /// How many rows `rhs` can fall before hitting a block or the floor, or [None] if it can't be placed.
+    fn rows_to_fall(&self, rhs: &Self) -> Option<usize> {
+        if !self.can_place(rhs) {
+            return None;
+        }
+        let mut piece = rhs.clone();
+        // bound by HEIGHT to catch an empty rhs
+        for rows_fallen in 0..HEIGHT {
+            match piece.try_bump_down() {
+                Some(bumped) if self.can_place(&bumped) => piece = bumped,
+                _ => return Some(rows_fallen),
+            }
         }
+        Some(HEIGHT)
     }
-}
 
-/// Construct a [Grid<_, _, CellState>], where `.` is [CellState::Unoccupied] and `#` is [CellState::Occupied]
-/// ```
-/// use tetris::grid;
-/// grid![
-///     [. . . . ],
-///     [. # # . ],
-///     [. # # . ],
-///     [. . . . ],
-/// ];
-/// ```
-#[macro_export]
-macro_rules! grid {
-    ($([$($cell:tt)* $(,)?]),* $(,)?) => {
-        $crate::Grid {
-            rows:
-                [ // begin grid
-                    $([ // begin row
-                        $(
-                            grid!(@cell $cell),
-                        )*
-                    ]),* // end row
-                ] // end grid
+    /// How many rows [Grid::drop]ping `rhs` would fill, without changing the grid.
+    /// Returns [None] if `rhs` can't be placed.
+    pub fn simulate_clears(&self, rhs: Self) -> Option<usize> {
+        let mask = self.clone().drop(rhs)?.clear_mask();
+        Some(mask.into_iter().filter(|&full| full).count())
+    }
+
+    /// Play a piece: [Grid::drop] `rhs`, then [clear](Grid::clear_solid_rows) full rows.
+    /// Returns the new grid and the number of rows cleared, or [None] if `rhs` can't be placed.
+    /// ```
+    /// use tetris::grid;
+    /// assert_eq!(grid![
+    ///     [. . . ],
+    ///     [. . . ],
+    ///     [# . # ],
+    /// ].drop_and_clear(grid![
+    ///     [. # . ],
+    ///     [. # . ],
+    ///     [. . . ],
+    /// ]),
+    /// Some((grid![
+    ///     [. . . ],
+    ///     [. . . ],
+    ///     [. # . ],
+    /// ], 1)));
+    /// ```
+    pub fn drop_and_clear(self, rhs: Self) -> Option<(Self, usize)> {
+        let mut landed = self.drop(rhs)?;
+        let cleared = landed.clear_solid_rows();
+        Some((landed, cleared))
+    }
+
+    /// Play one move, with the semantics of the brief:
+    /// spawn `shape` at the top of the grid with its left edge in `column`, drop it, and clear full rows.
+    /// Each cell of the piece is filled with `CellT::from(shape)`.
+    /// ```
+    /// use tetris::{grid, BlockShape, CellState, Grid};
+    /// let grid = Grid::<4, 3, CellState>::default().play(BlockShape::T, 1).unwrap();
+    /// assert_eq!(grid, grid![
+    ///     [. . . .],
+    ///     [. # # #],
+    ///     [. . # .],
+    /// ]);
+    /// ```
+    pub fn play(self, shape: BlockShape, column: usize) -> Result<Self, PlayError>
+    where
+        CellT: From<BlockShape>,
+    {
+        self.play_oriented(shape, column, 0)
+    }
+
+    /// As [Grid::play], but turn the piece clockwise `rotation` times after it spawns, before dropping it.
+    /// Each turn uses [SRS wall kicks](Grid::rotate_cw_kicked_from) against the blocks already on the grid.
+    /// ```
+    /// use tetris::{grid, BlockShape, CellState, Grid};
+    /// let grid = Grid::<4, 4, CellState>::default().play_oriented(BlockShape::T, 1, 2).unwrap();
+    /// assert_eq!(grid, grid![
+    ///     [. . . .],
+    ///     [. . . .],
+    ///     [. . # .],
+    ///     [. # # #],
+    /// ]);
+    /// ```
+    pub fn play_oriented(
+        self,
+        shape: BlockShape,
+        column: usize,
+        rotation: u8,
+    ) -> Result<Self, PlayError>
+    where
+        CellT: From<BlockShape>,
+    {
+        self.play_and_count(shape, column, rotation)
+            .map(|(grid, _)| grid)
+    }
+
+    /// [Grid::play] each of `moves` in turn, returning the final grid and how many rows each move cleared.
+    ///
+    /// On failure, returns the grid before the failing move, and that move's index.
+    /// ```
+    /// use tetris::{BlockShape::*, CellState, Grid};
+    /// let board = Grid::<4, 3, CellState>::default();
+    /// let (board, cleared) = board.drop_sequence([(I, 0), (Q, 0), (Q, 2)]).unwrap();
+    /// assert_eq!(cleared, [1, 0, 2]);
+    /// assert!(board.is_empty_board());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn drop_sequence(
+        self,
+        moves: impl IntoIterator<Item = (BlockShape, usize)>,
+    ) -> Result<(Self, Vec<usize>), (Self, usize)>
+    where
+        CellT: From<BlockShape>,
+    {
+        let mut board = self;
+        let mut cleared = Vec::new();
+        for (ix, (shape, column)) in moves.into_iter().enumerate() {
+            match board.clone().play_and_count(shape, column, 0) {
+                Ok((next, rows)) => {
+                    board = next;
+                    cleared.push(rows);
+                }
+                Err(_) => return Err((board, ix)),
             }
+        }
+        Ok((board, cleared))
+    }
+
+    /// [Grid::play_oriented], also returning how many rows were cleared.
+    fn play_and_count(
+        self,
+        shape: BlockShape,
+        column: usize,
+        rotation: u8,
+    ) -> Result<(Self, usize), PlayError>
+    where
+        CellT: From<BlockShape>,
+    {
+        let piece = tetromino::<WIDTH, HEIGHT>(shape).map(|cell| match is_occupied(&cell) {
+            true => CellT::from(shape),
+            false => CellT::default(),
+        });
+        let shape_width = piece
+            .bounding_box()
+            .map_or(0, |bbox| bbox.left + bbox.width);
+        if column > WIDTH.saturating_sub(shape_width) {
+            return Err(PlayError::HangsOffEdge {
+                shape,
+                column,
+                shape_width,
+                grid_width: WIDTH,
+            });
+        }
+        let (mut piece, mut col) = (piece >> column, column);
+        let mut orientation = Orientation::of_tetromino(shape);
+        for _ in 0..rotation % 4 {
+            (piece, col) = piece.rotate_cw_kicked_from(&self, col, orientation).ok_or(
+                PlayError::RotationBlocked {
+                    shape,
+                    column,
+                    rotation,
+                },
+            )?;
+            orientation = orientation.cw();
+        }
+        self.drop_and_clear(piece)
+            .ok_or(PlayError::SpawnBlocked { shape, column })
+    }
+
+    /// Every frame of [Grid::drop]ping `rhs`: the grid combined with `rhs`, at each row from where it starts to where it lands.
+    /// The last frame is the result of [Grid::drop], and there are no frames if `rhs` can't be placed.
+    /// ```
+    /// use tetris::grid;
+    /// let board = grid![
+    ///     [. . ],
+    ///     [. . ],
+    ///     [# . ],
+    /// ];
+    /// let piece = grid![
+    ///     [# # ],
+    ///     [. . ],
+    ///     [. . ],
+    /// ];
+    /// assert_eq!(board.drop_frames(piece), vec![
+    ///     grid![
+    ///         [# # ],
+    ///         [. . ],
+    ///         [# . ],
+    ///     ],
+    ///     grid![
+    ///         [. . ],
+    ///         [# # ],
+    ///         [# . ],
+    ///     ],
+    /// ]);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn drop_frames(self, rhs: Self) -> Vec<Self> {
+        let mut frames = Vec::new();
+        let mut piece = rhs;
+        // bound by HEIGHT to catch an empty rhs
+        for _ in 0..HEIGHT.max(1) {
+            match self.clone().bitand(piece.clone()) {
+                Ok(frame) => frames.push(frame),
+                Err(_) => break,
+            }
+            match piece.try_bump_down() {
+                Some(bumped) => piece = bumped,
+                None => break, // piece has hit the bottom of the grid
+            }
+        }
+        frames
+    }
+
+    /// Let every occupied cell fall independently to the bottom of its column, keeping cells in the same order.
+    /// Unlike [Grid::drop], pieces aren't kept rigid.
+    /// ```
+    /// use tetris::grid;
+    /// assert_eq!(grid![
+    ///     [# . . ],
+    ///     [# # . ],
+    ///     [. . . ],
+    ///     [. # # ],
+    /// ].settle_columns(),
+    /// grid![
+    ///     [. . . ],
+    ///     [. . . ],
+    ///     [# # . ],
+    ///     [# # # ],
+    /// ]);
+    /// ```
+    pub fn settle_columns(mut self) -> Self {
+        for col_ix in 0..WIDTH {
+            let mut write_ix = HEIGHT;
+            for read_ix in (0..HEIGHT).rev() {
+                if is_occupied(&self.rows[read_ix][col_ix]) {
+                    write_ix -= 1;
+                    let cell = mem::take(&mut self.rows[read_ix][col_ix]);
+                    self.rows[write_ix][col_ix] = cell;
+                }
+            }
+        }
+        self
+    }
+
+    /// Push every occupied cell to the left of its row, keeping cells in the same order.
+    /// The horizontal analogue of [Grid::settle_columns].
+    /// ```
+    /// use tetris::grid;
+    /// assert_eq!(grid![
+    ///     [. # . # ],
+    ///     [. . . # ],
+    /// ].settle_left(),
+    /// grid![
+    ///     [# # . . ],
+    ///     [# . . . ],
+    /// ]);
+    /// ```
+    pub fn settle_left(mut self) -> Self {
+        for row in &mut self.rows {
+            let mut write_ix = 0;
+            for read_ix in 0..WIDTH {
+                if is_occupied(&row[read_ix]) {
+                    row.swap(write_ix, read_ix);
+                    write_ix += 1;
+                }
+            }
+        }
+        self
+    }
+
+    /// Push every occupied cell to the right of its row, keeping cells in the same order.
+    /// See [Grid::settle_left].
+    pub fn settle_right(mut self) -> Self {
+        for row in &mut self.rows {
+            let mut write_ix = WIDTH;
+            for read_ix in (0..WIDTH).rev() {
+                if is_occupied(&row[read_ix]) {
+                    write_ix -= 1;
+                    row.swap(write_ix, read_ix);
+                }
+            }
+        }
+        self
+    }
+
+    /// Clear full rows by shifting taller rows down
+    /// ```
+    /// use tetris::grid;
+    /// assert_eq!(grid![
+    ///     [. . . . . . . . . .],
+    ///     [. . # # . . . . . .],
+    ///     [. . # # . . . . . .],
+    ///     [# # # # # # # # # #], // ← will be removed
+    ///     [# # . . # # # # # #],
+    /// ].with_solid_rows_cleared(),
+    /// grid![
+    ///     [. . . . . . . . . .], // ↳ fresh new row
+    ///     [. . . . . . . . . .],
+    ///     [. . # # . . . . . .], // ↓
+    ///     [. . # # . . . . . .], // ↓
+    ///     [# # . . # # # # # #],
+    /// ]
+    /// )
+    /// ```
+    pub fn with_solid_rows_cleared(mut self) -> Self {
+        self.clear_solid_rows();
+        self
+    }
+
+    /// Clear full rows in place, as in [Grid::with_solid_rows_cleared], returning the cleared rows, top to bottom.
+    #[cfg(feature = "std")]
+    pub fn extract_full_rows(&mut self) -> Vec<[CellT; WIDTH]> {
+        let full_rows = self
+            .rows
+            .iter()
+            .filter(|row| row.iter().all(is_occupied))
+            .cloned()
+            .collect();
+        self.clear_solid_rows();
+        full_rows
+    }
+
+    /// Clear full rows, then let each floating [connected component](Grid::connected_components) fall as a rigid group
+    /// until it rests, repeating until no more rows are full.
+    /// Unlike [Grid::settle_columns], groups aren't broken apart.
+    #[cfg(feature = "std")]
+    pub fn with_cascade_clears(mut self) -> Self {
+        loop {
+            self.clear_solid_rows();
+            while let Some(component) = self
+                .connected_components()
+                .into_iter()
+                .find(|component| self.component_can_fall(component))
+            {
+                let cells = component
+                    .iter()
+                    .map(|&(row_ix, col_ix)| mem::take(&mut self.rows[row_ix][col_ix]))
+                    .collect::<Vec<_>>();
+                for (&(row_ix, col_ix), cell) in component.iter().zip(cells) {
+                    self.rows[row_ix + 1][col_ix] = cell
+                }
+            }
+            if !self.clear_mask().contains(&true) {
+                return self;
+            }
+        }
+    }
+
+    /// Whether every cell in `component` (sorted) has room to move down a row.
+    #[cfg(feature = "std")]
+    fn component_can_fall(&self, component: &[(usize, usize)]) -> bool {
+        component.iter().all(|&(row_ix, col_ix)| {
+            row_ix + 1 < HEIGHT
+                && (is_empty(&self.rows[row_ix + 1][col_ix])
+                    || component.binary_search(&(row_ix + 1, col_ix)).is_ok())
+        })
+    }
+
+    /// Clear full rows in place, as in [Grid::with_solid_rows_cleared], returning how many were cleared.
+    /// ```
+    /// use tetris::grid;
+    /// let mut grid = grid![
+    ///     [. . . ],
+    ///     [# # # ], // ← will be removed
+    ///     [# # # ], // ← will be removed
+    ///     [# . . ],
+    /// ];
+    /// assert_eq!(grid.clear_solid_rows(), 2);
+    /// assert_eq!(grid, grid![
+    ///     [. . . ],
+    ///     [. . . ],
+    ///     [. . . ],
+    ///     [# . . ],
+    /// ]);
+    /// ```
+    pub fn clear_solid_rows(&mut self) -> usize {
+        // single pass: compact the non-full rows towards the bottom, behind a write cursor
+        let mut write_ix = HEIGHT;
+        for read_ix in (0..HEIGHT).rev() {
+            if !self.rows[read_ix].iter().all(is_occupied) {
+                write_ix -= 1;
+                self.rows.swap(write_ix, read_ix);
+            }
+        }
+        // everything above the cursor is a cleared row, or one we've already moved
+        for row in &mut self.rows[..write_ix] {
+            *row = Self::blank_row();
+        }
+        write_ix
+    }
+}
+
+/// How many times `cells` changes from one value to the other.
+fn transitions(cells: impl IntoIterator<Item = bool>) -> usize {
+    let mut cells = cells.into_iter();
+    let Some(mut previous) = cells.next() else {
+        return 0;
     };
-    (@cell #) => {
-        $crate::CellState::Occupied
-    };
-    (@cell .) => {
-        $crate::CellState::Unoccupied
-    };
+    cells
+        .filter(|&cell| cell != mem::replace(&mut previous, cell))
+        .count()
 }
 
-#[cfg(test)]
-mod tests {
-    use std::ops::{BitAnd, Shr};
+/// The result of [Grid::try_drop].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DropOutcome<T> {
+    /// The piece was placed and fell to rest, this is the combined grid.
+    Landed(T),
+    /// The piece couldn't be placed where it started.
+    Blocked,
+}
 
-    use super::*;
+/// The result of [Grid::hard_drop].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HardDropResult<T> {
+    /// The grid, with the piece combined into it.
+    pub board: T,
+    /// The piece alone, where it came to rest.
+    pub landed_piece: T,
+    /// How many rows the piece fell.
+    pub rows_fallen: usize,
+}
 
-    #[test]
-    fn shr_empty() {
-        let _: Grid<0, 0, CellState> = grid![] >> 1;
-        let _: Grid<0, 1, CellState> = grid![[]] >> 1;
-        let _: Grid<1, 0, CellState> = grid![] >> 1;
+/// The smallest rectangle containing every occupied cell of a [Grid].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoundingBox {
+    pub top: usize,
+    pub left: usize,
+    pub height: usize,
+    pub width: usize,
+}
+
+impl<const WIDTH: usize, const HEIGHT: usize, CellT> Grid<WIDTH, HEIGHT, CellT>
+where
+    CellT: Default + PartialEq,
+{
+    /// Whether `self` and `other` have the same cells occupied, regardless of what's in them.
+    pub fn same_occupancy<Other>(&self, other: &Grid<WIDTH, HEIGHT, Other>) -> bool
+    where
+        Other: Default + PartialEq,
+    {
+        self.rows
+            .iter()
+            .flatten()
+            .zip(other.rows.iter().flatten())
+            .all(|(lhs, rhs)| is_occupied(lhs) == is_occupied(rhs))
+    }
+
+    /// Whether every cell in row `row_ix` is empty.
+    /// # Panics
+    /// - If `row_ix` is out of bounds.
+    pub fn row_is_empty(&self, row_ix: usize) -> bool {
+        self.rows[row_ix].iter().all(is_empty)
+    }
+
+    /// Whether any cell in the rows `range` is occupied.
+    /// ```
+    /// use tetris::grid;
+    /// let grid = grid![
+    ///     [. .],
+    ///     [. .],
+    ///     [# .],
+    /// ];
+    /// assert!(!grid.any_occupied_in_rows(0..2));
+    /// assert!(grid.any_occupied_in_rows(1..3));
+    /// ```
+    /// # Panics
+    /// - If `range` extends past `HEIGHT`.
+    pub fn any_occupied_in_rows(&self, range: ops::Range<usize>) -> bool {
+        self.rows[range].iter().flatten().any(is_occupied)
+    }
+
+    /// Whether every cell in column `col_ix` is empty.
+    /// ```
+    /// use tetris::grid;
+    /// let grid = grid![
+    ///     [. # .],
+    ///     [. # .],
+    /// ];
+    /// assert!(grid.column_is_empty(0));
+    /// assert!(!grid.column_is_empty(1));
+    /// assert!(!grid.row_is_empty(1));
+    /// ```
+    /// # Panics
+    /// - If `col_ix` is out of bounds.
+    pub fn column_is_empty(&self, col_ix: usize) -> bool {
+        assert!(col_ix < WIDTH, "column {col_ix} is out of bounds");
+        self.rows.iter().all(|row| is_empty(&row[col_ix]))
+    }
+
+    /// As [std::ops::Shr](struct.Grid.html#impl-Shr<usize>-for-Grid<WIDTH%2C%20HEIGHT%2C%20CellT>),
+    /// but returns [None] rather than pushing any occupied cells off the right edge.
+    /// ```
+    /// use tetris::grid;
+    /// assert_eq!(grid![[# . .]].checked_shr(2), Some(grid![[. . #]]));
+    /// assert_eq!(grid![[# . .]].checked_shr(3), None);
+    /// ```
+    pub fn checked_shr(self, by: usize) -> Option<Self> {
+        (WIDTH.saturating_sub(by)..WIDTH)
+            .all(|col_ix| self.column_is_empty(col_ix))
+            .then(|| self >> by.min(WIDTH))
+    }
+
+    /// As [std::ops::Shl](struct.Grid.html#impl-Shl<usize>-for-Grid<WIDTH%2C%20HEIGHT%2C%20CellT>),
+    /// but returns [None] rather than pushing any occupied cells off the left edge.
+    pub fn checked_shl(self, by: usize) -> Option<Self> {
+        (0..by.min(WIDTH))
+            .all(|col_ix| self.column_is_empty(col_ix))
+            .then(|| self << by.min(WIDTH))
+    }
+
+    /// Whether no cell is occupied, e.g after a perfect clear.
+    pub fn is_empty_board(&self) -> bool {
+        self.rows.iter().flatten().all(is_empty)
+    }
+
+    /// Whether every occupied cell in `other` is also occupied in `self`.
+    /// ```
+    /// use tetris::grid;
+    /// let full = grid![
+    ///     [# # ],
+    ///     [# # ],
+    /// ];
+    /// let sparse = grid![
+    ///     [. . ],
+    ///     [# . ],
+    /// ];
+    /// assert!(full.contains(&sparse));
+    /// assert!(!sparse.contains(&full));
+    /// assert!(sparse.contains(&sparse));
+    /// ```
+    pub fn contains(&self, other: &Self) -> bool {
+        self.rows
+            .iter()
+            .flatten()
+            .zip(other.rows.iter().flatten())
+            .all(|(lhs, rhs)| is_occupied(lhs) || is_empty(rhs))
+    }
+
+    /// Whether every `(row_ix, col_ix)` in `coords` has the floor or an occupied cell directly beneath it.
+    /// ```
+    /// use tetris::grid;
+    /// let grid = grid![
+    ///     [. . .],
+    ///     [. # .],
+    /// ];
+    /// assert!(grid.is_supported(&[(0, 1), (1, 0)]));
+    /// assert!(!grid.is_supported(&[(0, 1), (0, 2)]));
+    /// ```
+    /// # Panics
+    /// - If any of `coords` are out of bounds.
+    pub fn is_supported(&self, coords: &[(usize, usize)]) -> bool {
+        coords.iter().all(|&(row_ix, col_ix)| {
+            assert!(
+                row_ix < HEIGHT && col_ix < WIDTH,
+                "({row_ix}, {col_ix}) is out of bounds"
+            );
+            row_ix + 1 == HEIGHT || is_occupied(&self.rows[row_ix + 1][col_ix])
+        })
+    }
+
+    /// Like [std::ops::BitAnd](struct.Grid.html#impl-BitAnd<Grid<WIDTH%2C%20HEIGHT%2C%20CellT>>-for-Grid<WIDTH%2C%20HEIGHT%2C%20CellT>),
+    /// but on failure, reports every collision rather than just the first, in row-major order.
+    #[cfg(feature = "std")]
+    pub fn try_combine_all(mut self, rhs: Self) -> Result<Self, Vec<WouldClobber>> {
+        let mut clobbers = Vec::new();
+        for row_ix in 0..HEIGHT {
+            for col_ix in 0..WIDTH {
+                if is_occupied(&self.rows[row_ix][col_ix]) && is_occupied(&rhs.rows[row_ix][col_ix])
+                {
+                    clobbers.push(WouldClobber { row_ix, col_ix })
+                }
+            }
+        }
+        if !clobbers.is_empty() {
+            return Err(clobbers);
+        }
+        self |= rhs;
+        Ok(self)
+    }
+
+    /// In-place [std::ops::BitAnd](struct.Grid.html#impl-BitAnd<Grid<WIDTH%2C%20HEIGHT%2C%20CellT>>-for-Grid<WIDTH%2C%20HEIGHT%2C%20CellT>).
+    /// On failure, `self` is left unchanged.
+    pub fn and_assign(&mut self, rhs: Self) -> Result<(), WouldClobber> {
+        for row_ix in 0..HEIGHT {
+            for col_ix in 0..WIDTH {
+                if is_occupied(&self.rows[row_ix][col_ix]) && is_occupied(&rhs.rows[row_ix][col_ix])
+                {
+                    return Err(WouldClobber { row_ix, col_ix });
+                }
+            }
+        }
+        *self |= rhs;
+        Ok(())
+    }
+
+    /// Overlay `rhs`, moved down by `row_offset` and right by `col_offset`, which may be negative.
+    /// Cells of `rhs` which end up off the grid are discarded, and where both grids are occupied, `self`'s cell is kept.
+    /// ```
+    /// use tetris::grid;
+    /// assert_eq!(
+    ///     grid![
+    ///         [. . .],
+    ///         [. . #],
+    ///     ]
+    ///     .place_clipped(grid![[. # #], [. . .]], 1, 0),
+    ///     grid![
+    ///         [. . .],
+    ///         [. # #],
+    ///     ]
+    /// );
+    /// ```
+    pub fn place_clipped(mut self, mut rhs: Self, row_offset: isize, col_offset: isize) -> Self {
+        for row_ix in 0..HEIGHT {
+            for col_ix in 0..WIDTH {
+                let (Some(to_row), Some(to_col)) = (
+                    row_ix.checked_add_signed(row_offset),
+                    col_ix.checked_add_signed(col_offset),
+                ) else {
+                    continue;
+                };
+                if to_row < HEIGHT && to_col < WIDTH && is_empty(&self.rows[to_row][to_col]) {
+                    self.rows[to_row][to_col] = mem::take(&mut rhs.rows[row_ix][col_ix])
+                }
+            }
+        }
+        self
+    }
+
+    /// Groups of occupied cells which touch above, below, left or right, as `(row, column)`.
+    /// Components are ordered by their topmost-leftmost cell, and each component's cells are listed row by row.
+    /// ```
+    /// use tetris::grid;
+    /// let grid = grid![
+    ///     [# . .],
+    ///     [# . #],
+    ///     [. # #],
+    /// ];
+    /// assert_eq!(
+    ///     grid.connected_components(),
+    ///     [vec![(0, 0), (1, 0)], vec![(1, 2), (2, 1), (2, 2)]]
+    /// );
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn connected_components(&self) -> Vec<Vec<(usize, usize)>> {
+        let mut seen = [[false; WIDTH]; HEIGHT];
+        let mut components = Vec::new();
+        for ((row_ix, col_ix), cell) in self.cells() {
+            if seen[row_ix][col_ix] || is_empty(cell) {
+                continue;
+            }
+            seen[row_ix][col_ix] = true;
+            let mut component = Vec::new();
+            let mut to_visit = vec![(row_ix, col_ix)];
+            while let Some((row_ix, col_ix)) = to_visit.pop() {
+                component.push((row_ix, col_ix));
+                for (row_ix, col_ix) in self.neighbors(row_ix, col_ix) {
+                    if !seen[row_ix][col_ix] && is_occupied(&self.rows[row_ix][col_ix]) {
+                        seen[row_ix][col_ix] = true;
+                        to_visit.push((row_ix, col_ix))
+                    }
+                }
+            }
+            component.sort();
+            components.push(component)
+        }
+        components
+    }
+
+    /// The indices of every row where all cells are occupied, top to bottom.
+    #[cfg(feature = "std")]
+    pub fn full_row_indices(&self) -> Vec<usize> {
+        (0..HEIGHT)
+            .filter(|&row_ix| self.rows[row_ix].iter().all(is_occupied))
+            .collect()
+    }
+
+    /// Which rows are full, and would be removed by [Grid::with_solid_rows_cleared].
+    /// Like [Grid::full_row_indices], but doesn't allocate.
+    /// ```
+    /// use tetris::grid;
+    /// assert_eq!(
+    ///     grid![
+    ///         [# . .],
+    ///         [# # #],
+    ///         [. # .],
+    ///     ]
+    ///     .clear_mask(),
+    ///     [false, true, false]
+    /// );
+    /// ```
+    pub fn clear_mask(&self) -> [bool; HEIGHT] {
+        self.row_fill_counts().map(|count| count == WIDTH)
+    }
+
+    /// The number of occupied cells in each row, top to bottom.
+    /// ```
+    /// use tetris::grid;
+    /// assert_eq!(
+    ///     grid![
+    ///         [. . . .],
+    ///         [. # . .],
+    ///         [# # . #],
+    ///         [# # # #],
+    ///     ]
+    ///     .row_fill_counts(),
+    ///     [0, 1, 3, 4]
+    /// );
+    /// ```
+    pub fn row_fill_counts(&self) -> [usize; HEIGHT] {
+        array![row_ix => self.rows[row_ix].iter().filter(|cell| is_occupied(*cell)).count(); HEIGHT]
+    }
+
+    /// The number of occupied cells.
+    pub fn count_occupied(&self) -> usize {
+        self.rows
+            .iter()
+            .flatten()
+            .filter(|cell| is_occupied(*cell))
+            .count()
+    }
+
+    /// The fraction of cells which are occupied, or `0.0` for a grid with no cells.
+    /// ```
+    /// use tetris::{grid, Grid};
+    /// assert_eq!(
+    ///     grid![
+    ///         [. #],
+    ///         [# .],
+    ///     ]
+    ///     .occupancy_ratio(),
+    ///     0.5
+    /// );
+    /// assert_eq!(Grid::<0, 0>::default().occupancy_ratio(), 0.0);
+    /// ```
+    pub fn occupancy_ratio(&self) -> f64 {
+        match WIDTH * HEIGHT {
+            0 => 0.0,
+            cells => self.count_occupied() as f64 / cells as f64,
+        }
+    }
+
+    /// The height of the highest occupied cell in each column, left to right, where `0` is an empty column.
+    /// ```
+    /// use tetris::grid;
+    /// assert_eq!(
+    ///     grid![
+    ///         [. # .],
+    ///         [. . .],
+    ///         [# . .],
+    ///     ]
+    ///     .column_heights(),
+    ///     [1, 3, 0]
+    /// );
+    /// ```
+    pub fn column_heights(&self) -> [usize; WIDTH] {
+        array![col_ix => (0..HEIGHT)
+            .find(|&row_ix| is_occupied(&self.rows[row_ix][col_ix]))
+            .map_or(0, |row_ix| HEIGHT - row_ix); WIDTH]
+    }
+
+    /// The number of empty cells with an occupied cell somewhere above them in the same column.
+    /// ```
+    /// use tetris::grid;
+    /// assert_eq!(
+    ///     grid![
+    ///         [. # .],
+    ///         [# . .],
+    ///         [. . #],
+    ///     ]
+    ///     .holes(),
+    ///     3
+    /// );
+    /// ```
+    pub fn holes(&self) -> usize {
+        (0..WIDTH)
+            .map(|col_ix| {
+                self.rows
+                    .iter()
+                    .map(|row| &row[col_ix])
+                    .skip_while(|cell| is_empty(*cell))
+                    .filter(|cell| is_empty(*cell))
+                    .count()
+            })
+            .sum()
+    }
+
+    /// The occupied cells with an empty cell directly beneath them, sheltering a [hole](Grid::holes),
+    /// as `(row, column)` in row-major order.
+    /// ```
+    /// use tetris::grid;
+    /// let grid = grid![
+    ///     [# # .],
+    ///     [# . .],
+    ///     [# . #],
+    /// ];
+    /// assert_eq!(grid.overhang_cells(), [(0, 1)]);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn overhang_cells(&self) -> Vec<(usize, usize)> {
+        self.cells()
+            .filter(|&((row_ix, col_ix), cell)| {
+                is_occupied(cell)
+                    && self
+                        .rows
+                        .get(row_ix + 1)
+                        .is_some_and(|below| is_empty(&below[col_ix]))
+            })
+            .map(|(coords, _)| coords)
+            .collect()
+    }
+
+    /// The sum of the differences between neighbouring [column heights](Grid::column_heights).
+    pub fn bumpiness(&self) -> usize {
+        let heights = self.column_heights();
+        heights
+            .iter()
+            .zip(heights.iter().skip(1))
+            .map(|(left, right)| left.abs_diff(*right))
+            .sum()
+    }
+
+    /// The sum of the [column heights](Grid::column_heights), a common input to stacking heuristics.
+    /// ```
+    /// use tetris::grid;
+    /// let staircase = grid![
+    ///     [. # ],
+    ///     [# # ],
+    /// ];
+    /// assert_eq!(staircase.aggregate_height(), 1 + 2);
+    /// ```
+    pub fn aggregate_height(&self) -> usize {
+        self.column_heights().into_iter().sum()
+    }
+
+    /// How many times neighbouring cells in a row change between empty and occupied, summed over every row.
+    /// The walls count as occupied.
+    pub fn row_transitions(&self) -> usize {
+        self.rows
+            .iter()
+            .map(|row| {
+                transitions(
+                    core::iter::once(true)
+                        .chain(row.iter().map(is_occupied))
+                        .chain(core::iter::once(true)),
+                )
+            })
+            .sum()
+    }
+
+    /// How many times neighbouring cells in a column change between empty and occupied, summed over every column.
+    /// The floor counts as occupied, but the top of the grid is open.
+    pub fn column_transitions(&self) -> usize {
+        (0..WIDTH)
+            .map(|col_ix| {
+                transitions(
+                    self.rows
+                        .iter()
+                        .map(|row| is_occupied(&row[col_ix]))
+                        .chain(core::iter::once(true)),
+                )
+            })
+            .sum()
+    }
+
+    /// The `(column, depth)` of the deepest well: a column whose neighbours are both at least `depth` taller.
+    /// Edge columns only have one neighbour.
+    /// Returns [None] if there are no wells. Ties go to the leftmost column.
+    pub fn deepest_well(&self) -> Option<(usize, usize)> {
+        let heights = self.column_heights();
+        let mut deepest = None;
+        for col_ix in 0..WIDTH {
+            let left = col_ix.checked_sub(1).map(|ix| heights[ix]);
+            let right = heights.get(col_ix + 1).copied();
+            let walls = match (left, right) {
+                (Some(left), Some(right)) => left.min(right),
+                (Some(wall), None) | (None, Some(wall)) => wall,
+                (None, None) => continue,
+            };
+            let depth = walls.saturating_sub(heights[col_ix]);
+            if depth > deepest.map_or(0, |(_, deepest)| deepest) {
+                deepest = Some((col_ix, depth))
+            }
+        }
+        deepest
+    }
+
+    /// Whether any cell is occupied above the bottom `playable_height` rows.
+    /// ```
+    /// use tetris::grid;
+    /// let grid = grid![
+    ///     [. .],
+    ///     [# .],
+    ///     [# #],
+    /// ];
+    /// assert!(grid.overflowed_playfield(1));
+    /// assert!(!grid.overflowed_playfield(2));
+    /// ```
+    pub fn overflowed_playfield(&self, playable_height: usize) -> bool {
+        self.rows[..HEIGHT.saturating_sub(playable_height)]
+            .iter()
+            .flatten()
+            .any(is_occupied)
+    }
+
+    /// Try and move this grid up, fail if the first row is non-empty.
+    /// See [Grid::try_bump_down].
+    pub fn try_bump_up(mut self) -> Option<Self> {
+        match HEIGHT.checked_sub(1) {
+            Some(_) if self.row_is_empty(0) => {
+                self.rows.rotate_left(1);
+                Some(self)
+            }
+            Some(_) => None,
+            None => Some(self),
+        }
+    }
+
+    /// Insert `rows` at the bottom of the grid, one at a time, pushing the stack up.
+    /// Returns [None] if an occupied cell would be pushed off the top.
+    /// ```
+    /// use tetris::{grid, CellState::{Occupied as X, Unoccupied as O}};
+    /// assert_eq!(grid![
+    ///     [. . . ],
+    ///     [. . . ],
+    ///     [. # . ],
+    /// ].push_garbage([[X, O, X]]),
+    /// Some(grid![
+    ///     [. . . ],
+    ///     [. # . ], // ↑
+    ///     [# . # ], // ← garbage
+    /// ]));
+    /// ```
+    pub fn push_garbage(mut self, rows: impl IntoIterator<Item = [CellT; WIDTH]>) -> Option<Self> {
+        for row in rows {
+            self = self.try_bump_up()?;
+            match self.rows.last_mut() {
+                Some(last_row) => *last_row = row,
+                None if row.iter().all(is_empty) => (),
+                None => return None, // no room for the garbage itself
+            }
+        }
+        Some(self)
+    }
+
+    /// The [BoundingBox] of the occupied cells, or [None] if there are none.
+    /// ```
+    /// use tetris::{grid, BoundingBox};
+    /// assert_eq!(grid![
+    ///     [. . . . ],
+    ///     [. . # . ],
+    ///     [. # # . ],
+    /// ].bounding_box(),
+    /// Some(BoundingBox {
+    ///     top: 1,
+    ///     left: 1,
+    ///     height: 2,
+    ///     width: 2,
+    /// }));
+    /// ```
+    pub fn bounding_box(&self) -> Option<BoundingBox> {
+        let row_is_occupied = |row_ix: &usize| self.rows[*row_ix].iter().any(is_occupied);
+        let col_is_occupied =
+            |col_ix: &usize| self.rows.iter().any(|row| is_occupied(&row[*col_ix]));
+        let top = (0..HEIGHT).find(row_is_occupied)?;
+        let bottom = (0..HEIGHT).rev().find(row_is_occupied)?;
+        let left = (0..WIDTH).find(col_is_occupied)?;
+        let right = (0..WIDTH).rev().find(col_is_occupied)?;
+        Some(BoundingBox {
+            top,
+            left,
+            height: bottom - top + 1,
+            width: right - left + 1,
+        })
+    }
+
+    /// How high `landed_piece` (e.g from [Grid::ghost]) came to rest, as used by Dellacherie's heuristic:
+    /// the height of the middle of its [BoundingBox], where the bottom row is height `1`.
+    /// An empty piece has a landing height of `0.0`.
+    /// ```
+    /// use tetris::{grid, Grid};
+    /// let landed = grid![
+    ///     [. . .],
+    ///     [# # .],
+    ///     [. # #],
+    ///     [. . .],
+    /// ];
+    /// // rows at heights 3 and 2
+    /// assert_eq!(Grid::landing_height(&landed), 2.5);
+    /// ```
+    pub fn landing_height(landed_piece: &Self) -> f64 {
+        landed_piece.bounding_box().map_or(0.0, |bbox| {
+            let top_height = (HEIGHT - bbox.top) as f64;
+            top_height - (bbox.height - 1) as f64 / 2.0
+        })
+    }
+
+    /// Dellacherie's "eroded piece cells": the number of rows cleared, times the number of cells of `landed_piece` which were cleared.
+    /// `board_before_clear` is the board with `landed_piece` combined into it, before any rows are cleared.
+    /// ```
+    /// use tetris::{grid, Grid};
+    /// let piece = grid![
+    ///     [. . .],
+    ///     [# # .],
+    ///     [# # .],
+    /// ];
+    /// let before_clear = grid![
+    ///     [. . .],
+    ///     [# # #],
+    ///     [# # #],
+    /// ];
+    /// assert_eq!(Grid::eroded_cells(&before_clear, &piece), 2 * 4);
+    /// ```
+    pub fn eroded_cells(board_before_clear: &Self, landed_piece: &Self) -> usize {
+        let (rows, cells) = board_before_clear
+            .rows
+            .iter()
+            .zip(&landed_piece.rows)
+            .filter(|(row, _)| row.iter().all(is_occupied))
+            .fold((0, 0), |(rows, cells), (_, piece_row)| {
+                (
+                    rows + 1,
+                    cells + piece_row.iter().filter(|cell| is_occupied(*cell)).count(),
+                )
+            });
+        rows * cells
+    }
+}
+
+impl<const WIDTH: usize, const HEIGHT: usize, CellT> Grid<WIDTH, HEIGHT, CellT>
+where
+    CellT: Default,
+{
+    /// Copy into a grid of a different size, keeping cells anchored to the bottom-left corner,
+    /// since stacks grow from the bottom.
+    /// Cells which don't fit are dropped, and new cells are empty.
+    /// ```
+    /// use tetris::{grid, CellState, Grid};
+    /// let shrunk: Grid<2, 1, CellState> = grid![
+    ///     [# # #],
+    ///     [. # #],
+    /// ].resize();
+    /// assert_eq!(shrunk, grid![[. #]]);
+    /// ```
+    pub fn resize<const NEW_WIDTH: usize, const NEW_HEIGHT: usize>(
+        &self,
+    ) -> Grid<NEW_WIDTH, NEW_HEIGHT, CellT>
+    where
+        CellT: Clone,
+    {
+        Grid {
+            rows: array![row_ix => array![col_ix => (row_ix + HEIGHT)
+                .checked_sub(NEW_HEIGHT)
+                .and_then(|old_row_ix| self.rows.get(old_row_ix)?.get(col_ix).cloned())
+                .unwrap_or_default(); NEW_WIDTH]; NEW_HEIGHT],
+        }
+    }
+
+    /// Empty every cell in the row at `row_ix`.
+    /// # Panics
+    /// - If `row_ix` is out of bounds
+    pub fn clear_row(&mut self, row_ix: usize) {
+        for cell in self.rows[row_ix].iter_mut() {
+            *cell = CellT::default()
+        }
+    }
+
+    /// Empty every cell in the column at `col_ix`.
+    /// # Panics
+    /// - If `col_ix` is out of bounds
+    pub fn clear_column(&mut self, col_ix: usize) {
+        assert!(col_ix < WIDTH, "column {col_ix} is out of bounds");
+        for row in self.rows.iter_mut() {
+            row[col_ix] = CellT::default()
+        }
+    }
+
+    /// Empty every cell for which `keep(row_ix, col_ix, cell)` is `false`.
+    /// ```
+    /// use tetris::grid;
+    /// let mut grid = grid![
+    ///     [# # #],
+    ///     [# . #],
+    /// ];
+    /// grid.retain_cells(|_, col_ix, _| col_ix != 1);
+    /// assert_eq!(grid, grid![
+    ///     [# . #],
+    ///     [# . #],
+    /// ]);
+    /// ```
+    pub fn retain_cells(&mut self, keep: impl Fn(usize, usize, &CellT) -> bool) {
+        self.for_each_cell_mut(|row_ix, col_ix, cell| {
+            if !keep(row_ix, col_ix, cell) {
+                *cell = CellT::default()
+            }
+        })
+    }
+}
+
+impl<const SIZE: usize, CellT> Grid<SIZE, SIZE, CellT>
+where
+    CellT: Default + Clone + PartialEq,
+{
+    /// Whether a quarter turn with [Grid::rotate_cw] leaves the shape unchanged.
+    /// As with [Grid::distinct_rotations], shapes are compared after moving them to the top-left corner.
+    /// ```
+    /// use tetris::{tetromino, BlockShape};
+    /// assert!(tetromino::<4, 4>(BlockShape::Q).is_symmetric_under_rotation());
+    /// ```
+    pub fn is_symmetric_under_rotation(&self) -> bool {
+        self.clone().rotate_cw().into_top_left() == self.clone().into_top_left()
+    }
+
+    /// The distinct states reachable by successive [Grid::rotate_cw]s, starting with `self`, moved to the top-left.
+    /// Each state is moved to the top-left corner, so that states are compared by shape rather than position.
+    /// ```
+    /// use tetris::grid;
+    /// let s = grid![
+    ///     [. # #],
+    ///     [# # .],
+    ///     [. . .],
+    /// ];
+    /// assert_eq!(s.distinct_rotations(), [
+    ///     s,
+    ///     grid![
+    ///         [# . .],
+    ///         [# # .],
+    ///         [. # .],
+    ///     ],
+    /// ]);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn distinct_rotations(self) -> Vec<Self> {
+        let mut rotations: Vec<Self> = Vec::with_capacity(4);
+        let mut rotation = self;
+        for _ in 0..4 {
+            let next = rotation.clone().rotate_cw();
+            let normalized = rotation.into_top_left();
+            if !rotations.contains(&normalized) {
+                rotations.push(normalized)
+            }
+            rotation = next;
+        }
+        rotations
+    }
+
+    /// Move the occupied cells so that their [BoundingBox] starts at the top-left corner.
+    fn into_top_left(mut self) -> Self {
+        let Some(BoundingBox { top, left, .. }) = self.bounding_box() else {
+            return self;
+        };
+        let mut moved = Self::default();
+        for row_ix in top..SIZE {
+            for col_ix in left..SIZE {
+                moved.rows[row_ix - top][col_ix - left] = mem::take(&mut self.rows[row_ix][col_ix]);
+            }
+        }
+        moved
+    }
+}
+
+/// The seven tetrominoes, named for the letter they resemble (`Q` is the square "O" piece).
+#[derive(Debug, EnumString, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub enum BlockShape {
+    Q,
+    Z,
+    S,
+    T,
+    I,
+    L,
+    J,
+}
+
+impl BlockShape {
+    /// Every shape, once.
+    pub const ALL: [Self; 7] = [
+        BlockShape::Q,
+        BlockShape::Z,
+        BlockShape::S,
+        BlockShape::T,
+        BlockShape::I,
+        BlockShape::L,
+        BlockShape::J,
+    ];
+}
+
+/// Whether a `width` × `height` grid is big enough for every [BlockShape]:
+/// `I` is 4 wide, and `J` and `L` are 3 tall.
+/// ```
+/// use tetris::fits_all_tetrominoes;
+/// assert!(fits_all_tetrominoes(10, 103));
+/// assert!(!fits_all_tetrominoes(3, 3));
+/// ```
+pub const fn fits_all_tetrominoes(width: usize, height: usize) -> bool {
+    width >= 4 && height >= 3
+}
+
+/// Place a [BlockShape] in the top-left of a new [Grid]
+/// # Compile errors
+/// - If the grid is too small to fit every shape, see [fits_all_tetrominoes]
+/// ```compile_fail
+/// use tetris::{tetromino, BlockShape, Grid};
+/// let _: Grid<2, 2> = tetromino(BlockShape::Q);
+/// ```
+pub fn tetromino<const WIDTH: usize, const HEIGHT: usize>(
+    shape: BlockShape,
+) -> Grid<WIDTH, HEIGHT, CellState> {
+    use CellState::Occupied as X;
+    const {
+        assert!(
+            fits_all_tetrominoes(WIDTH, HEIGHT),
+            "grid is too small to fit every tetromino"
+        )
+    };
+    let coords = match shape {
+        BlockShape::Q => [(0, 0), (0, 1), (1, 0), (1, 1)],
+        BlockShape::Z => [(0, 0), (0, 1), (1, 1), (1, 2)],
+        BlockShape::S => [(0, 1), (0, 2), (1, 0), (1, 1)],
+        BlockShape::T => [(0, 0), (0, 1), (0, 2), (1, 1)],
+        BlockShape::I => [(0, 0), (0, 1), (0, 2), (0, 3)],
+        BlockShape::L => [(0, 0), (1, 0), (2, 0), (2, 1)],
+        BlockShape::J => [(0, 1), (1, 1), (2, 1), (2, 0)],
+    };
+    Grid::from_coords(coords, X)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CellState {
+    // The reason we do a song and dance with `Default` above is because
+    // putting information in `Occupied` is now trivial - a likely extension for
+    // the business (e.g adding colours)
+    Occupied,
+    #[default]
+    Unoccupied,
+}
+
+impl fmt::Debug for CellState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Occupied => write!(f, "#"),
+            Self::Unoccupied => write!(f, "."),
+        }
+    }
+}
+
+/// A cell filled by any shape is simply occupied.
+/// Use `Option<BlockShape>` as the `CellT` to remember which shape it was.
+impl From<BlockShape> for CellState {
+    fn from(_: BlockShape) -> Self {
+        Self::Occupied
+    }
+}
+
+/// Test patterns.
+impl<const WIDTH: usize, const HEIGHT: usize> Grid<WIDTH, HEIGHT, CellState> {
+    /// Alternating cells, starting with an occupied top-left cell.
+    /// ```
+    /// use tetris::{grid, Grid};
+    /// assert_eq!(Grid::checkerboard(), grid![
+    ///     [# .],
+    ///     [. #],
+    /// ]);
+    /// ```
+    pub fn checkerboard() -> Self {
+        Self::pattern(|row_ix, col_ix| (row_ix + col_ix) % 2 == 0)
+    }
+
+    /// Every cell occupied.
+    /// ```
+    /// use tetris::{grid, Grid};
+    /// assert_eq!(Grid::full(), grid![
+    ///     [# #],
+    ///     [# #],
+    /// ]);
+    /// ```
+    pub fn full() -> Self {
+        Self::pattern(|_, _| true)
+    }
+
+    /// Alternating columns, starting with an occupied leftmost column.
+    /// ```
+    /// use tetris::{grid, Grid};
+    /// assert_eq!(Grid::column_striped(), grid![
+    ///     [# . #],
+    ///     [# . #],
+    /// ]);
+    /// ```
+    pub fn column_striped() -> Self {
+        Self::pattern(|_, col_ix| col_ix % 2 == 0)
+    }
+
+    fn pattern(occupied: impl Fn(usize, usize) -> bool) -> Self {
+        Self {
+            rows: array![row_ix => array![col_ix => match occupied(row_ix, col_ix) {
+                true => CellState::Occupied,
+                false => CellState::Unoccupied,
+            }; WIDTH]; HEIGHT],
+        }
+    }
+}
+
+/// `true` is [CellState::Occupied].
+impl<const WIDTH: usize, const HEIGHT: usize> From<[[bool; WIDTH]; HEIGHT]>
+    for Grid<WIDTH, HEIGHT, CellState>
+{
+    fn from(bools: [[bool; WIDTH]; HEIGHT]) -> Self {
+        Self {
+            rows: bools.map(|row| {
+                row.map(|occupied| match occupied {
+                    true => CellState::Occupied,
+                    false => CellState::Unoccupied,
+                })
+            }),
+        }
+    }
+}
+
+/// [CellState::Occupied] is `true`.
+impl<const WIDTH: usize, const HEIGHT: usize> From<Grid<WIDTH, HEIGHT, CellState>>
+    for [[bool; WIDTH]; HEIGHT]
+{
+    fn from(grid: Grid<WIDTH, HEIGHT, CellState>) -> Self {
+        grid.rows.map(|row| row.map(|cell| is_occupied(&cell)))
+    }
+}
+
+/// Rows which don't fit a [Grid] exactly, see [Grid::assert_dimensions].
+#[cfg(feature = "std")]
+#[derive(Debug, thiserror::Error, PartialEq, Eq, Clone, Copy)]
+pub enum DimensionMismatch {
+    #[error("found {actual} rows, but the grid is {expected} high")]
+    Height { expected: usize, actual: usize },
+    #[error("row {row_ix} has {actual} cells, but the grid is {expected} wide")]
+    Width {
+        row_ix: usize,
+        expected: usize,
+        actual: usize,
+    },
+}
+
+#[cfg(feature = "std")]
+impl<const WIDTH: usize, const HEIGHT: usize, CellT> Grid<WIDTH, HEIGHT, CellT> {
+    /// Check that `rows` has exactly `HEIGHT` rows of exactly `WIDTH` cells, reporting the first mismatch.
+    pub fn assert_dimensions(rows: &[Vec<CellT>]) -> Result<(), DimensionMismatch> {
+        if rows.len() != HEIGHT {
+            return Err(DimensionMismatch::Height {
+                expected: HEIGHT,
+                actual: rows.len(),
+            });
+        }
+        match rows.iter().position(|row| row.len() != WIDTH) {
+            Some(row_ix) => Err(DimensionMismatch::Width {
+                row_ix,
+                expected: WIDTH,
+                actual: rows[row_ix].len(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Check the shape of the backing storage in debug builds.
+    ///
+    /// The arrays backing [Grid] can't be malformed, so this only documents the invariant,
+    /// for the sake of any future backing that could be.
+    pub fn debug_assert_well_formed(&self) {
+        debug_assert_eq!(self.rows.len(), HEIGHT);
+        debug_assert!(self.rows.iter().all(|row| row.len() == WIDTH));
+    }
+}
+
+/// Checked with [Grid::assert_dimensions].
+/// ```
+/// use tetris::{grid, CellState::*, Grid};
+/// assert_eq!(
+///     Grid::try_from(vec![vec![Unoccupied, Occupied]]),
+///     Ok(grid![[. #]])
+/// );
+/// ```
+#[cfg(feature = "std")]
+impl<const WIDTH: usize, const HEIGHT: usize, CellT> TryFrom<Vec<Vec<CellT>>>
+    for Grid<WIDTH, HEIGHT, CellT>
+{
+    type Error = DimensionMismatch;
+
+    fn try_from(rows: Vec<Vec<CellT>>) -> Result<Self, Self::Error> {
+        Self::assert_dimensions(&rows)?;
+        let rows = rows
+            .into_iter()
+            .map(|row| match <[CellT; WIDTH]>::try_from(row) {
+                Ok(row) => row,
+                Err(_) => unreachable!("widths were checked above"),
+            })
+            .collect::<Vec<_>>();
+        match <[[CellT; WIDTH]; HEIGHT]>::try_from(rows) {
+            Ok(rows) => Ok(Self { rows }),
+            Err(_) => unreachable!("height was checked above"),
+        }
+    }
+}
+
+/// Construct a [Grid<_, _, CellState>], where `.` is [CellState::Unoccupied] and `#` is [CellState::Occupied]
+/// ```
+/// use tetris::grid;
+/// grid![
+///     [. . . . ],
+///     [. # # . ],
+///     [. # # . ],
+///     [. . . . ],
+/// ];
+/// ```
+#[macro_export]
+macro_rules! grid {
+    ($([$($cell:tt)* $(,)?]),* $(,)?) => {
+        $crate::Grid {
+            rows:
+                [ // begin grid
+                    $([ // begin row
+                        $(
+                            grid!(@cell $cell),
+                        )*
+                    ]),* // end row
+                ] // end grid
+            }
+    };
+    (@cell #) => {
+        $crate::CellState::Occupied
+    };
+    (@cell .) => {
+        $crate::CellState::Unoccupied
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::{BitAnd, Shr};
+
+    use super::*;
+    use proptest::prelude::*;
+
+    fn occupied_coords<const WIDTH: usize, const HEIGHT: usize>(
+        grid: &Grid<WIDTH, HEIGHT>,
+    ) -> Vec<(usize, usize)> {
+        grid.cells()
+            .filter(|(_, cell)| is_occupied(*cell))
+            .map(|(coords, _)| coords)
+            .collect()
+    }
+
+    proptest! {
+        #[test]
+        fn drop_single_cell_invariants(
+            board in any::<[[bool; 4]; 6]>(),
+            piece_row_ix in 0..6usize,
+            piece_col_ix in 0..4usize,
+        ) {
+            let board = Grid::<4, 6>::from(board);
+            let mut piece = Grid::default();
+            piece.rows[piece_row_ix][piece_col_ix] = CellState::Occupied;
+            if let Some(dropped) = board.drop(piece) {
+                let before = occupied_coords(&board);
+                let after = occupied_coords(&dropped);
+                // the piece's cell count is preserved
+                prop_assert_eq!(after.len(), before.len() + 1);
+                // the board's cells are untouched
+                prop_assert!(before.iter().all(|coord| after.contains(coord)));
+                // the piece only moves down
+                let landed: Vec<_> = after.iter().filter(|coord| !before.contains(coord)).collect();
+                prop_assert_eq!(landed.len(), 1);
+                prop_assert_eq!(landed[0].1, piece_col_ix);
+                prop_assert!(landed[0].0 >= piece_row_ix);
+            }
+        }
+    }
+
+    #[test]
+    fn shr_empty() {
+        let _: Grid<0, 0, CellState> = grid![] >> 1;
+        let _: Grid<0, 1, CellState> = grid![[]] >> 1;
+        let _: Grid<1, 0, CellState> = grid![] >> 1;
+    }
+
+    #[test]
+    fn checked_shifts() {
+        let grid = grid![
+            [. # .],
+            [. # .],
+        ];
+        assert_eq!(grid.checked_shl(1), Some(grid![[# . .], [# . .]]));
+        assert_eq!(grid.checked_shr(1), Some(grid![[. . #], [. . #]]));
+        assert_eq!(grid.checked_shl(2), None);
+        assert_eq!(grid.checked_shr(2), None);
+        assert_eq!(grid.checked_shr(0), Some(grid));
+        assert_eq!(
+            Grid::<3, 2>::default().checked_shl(usize::MAX),
+            Some(Grid::default())
+        );
+    }
+
+    #[test]
+    fn shift_by_agrees_with_shr_and_shl() {
+        let grid = grid![
+            [. # .],
+            [# # #],
+        ];
+        assert_eq!(grid.shift_by(0, 1), grid >> 1);
+        assert_eq!(grid.shift_by(0, -2), grid << 2);
+        assert_eq!(grid.shift_by(1, 0), grid![[. . .], [. # .]]);
+        assert_eq!(grid.shift_by(isize::MIN, isize::MAX), Grid::default());
+    }
+
+    #[test]
+    fn checked_shift_by_off_the_floor() {
+        let grid = grid![
+            [. . .],
+            [. # .],
+        ];
+        assert_eq!(grid.checked_shift_by(1, 0), None);
+        assert_eq!(grid.checked_shift_by(-1, 1), Some(grid![[. . #], [. . .]]));
+        assert_eq!(grid.checked_shift_by(-1, 2), None);
+        assert_eq!(
+            Grid::<3, 2>::default().checked_shift_by(isize::MAX, 0),
+            Some(Grid::default())
+        );
+    }
+
+    #[test]
+    fn bitand() {
+        assert_eq!(grid![[#]].bitand(grid![[.]]), Ok(grid![[#]]));
+    }
+
+    #[test]
+    fn cant_shift_off_edge() {
+        assert_eq!(grid![[#]].try_shift_down(1), None)
+    }
+
+    #[test]
+    fn shift_blocked_by_bottom_row() {
+        assert_eq!(
+            grid![[.], [#]].try_shift_down_checked(2),
+            Err(ShiftBlocked {
+                at_step: 0,
+                blocking_row: 1
+            })
+        );
+        assert_eq!(
+            grid![[#], [.]].try_shift_down_checked(1),
+            Ok(grid![[.], [#]])
+        );
+    }
+
+    #[test]
+    fn clobbering() {
+        assert_eq!(
+            grid![
+                [. . . .], // row 0
+                [. # # .], // row 1
+                [. . . .], // row 2
+            ]
+            .bitand(grid![
+                [. . . .],
+                [. . # .],
+                [. . . .],
+            ]),
+            Err(WouldClobber {
+                row_ix: 1,
+                col_ix: 2
+            })
+        )
+    }
+
+    #[test]
+    fn drop_single() {
+        assert_eq!(grid!([.]).drop(grid!([#])), Some(grid!([#])))
+    }
+
+    #[test]
+    fn drop_through_air() {
+        assert_eq!(
+            grid!([.], [.], [.]).drop(grid!([#], [.], [.])),
+            Some(grid!([.], [.], [#]))
+        )
+    }
+
+    #[test]
+    fn drop_row_through_air() {
+        assert_eq!(
+            grid!([.], [.], [.]).drop_row_for_column(&grid!([#], [.], [.])),
+            Some(2)
+        );
+        assert_eq!(
+            grid!([.], [.], [#]).drop_row_for_column(&grid!([#], [#], [.])),
+            Some(0)
+        );
+        assert_eq!(grid!([.], [.]).drop_row_for_column(&grid!([.], [.])), None);
+    }
+
+    #[test]
+    fn hard_drop_through_air() {
+        assert_eq!(
+            grid!([.], [.], [.]).hard_drop(grid!([#], [.], [.])),
+            Some(HardDropResult {
+                board: grid!([.], [.], [#]),
+                landed_piece: grid!([.], [.], [#]),
+                rows_fallen: 2,
+            })
+        )
+    }
+
+    #[test]
+    fn drop_checked_overflow_or_blocked() {
+        let piece = grid![
+            [# .],
+            [. .],
+            [. .],
+        ];
+        let board = grid![
+            [. .],
+            [# .],
+            [# #],
+        ];
+        assert_eq!(
+            board.drop_checked(piece),
+            Ok(grid![
+                [# .],
+                [# .],
+                [# #],
+            ])
+        );
+        let overflowing = grid![
+            [# .],
+            [# .],
+            [# #],
+        ];
+        assert_eq!(overflowing.drop_checked(piece), Err(DropError::Overflow));
+        assert_eq!(
+            overflowing.drop_checked(piece >> 1),
+            Ok(grid![
+                [# .],
+                [# #],
+                [# #],
+            ])
+        );
+        let tall_piece = grid![
+            [. #],
+            [. #],
+            [. .],
+        ];
+        assert_eq!(
+            board.drop_checked(tall_piece),
+            Ok(grid![
+                [. #],
+                [# #],
+                [# #],
+            ])
+        );
+        assert_eq!(
+            board.drop_checked(grid![
+                [. .],
+                [# .],
+                [. .],
+            ]),
+            Err(DropError::SpawnBlocked)
+        );
+    }
+
+    #[test]
+    fn drop_checked_ignores_the_top_row_away_from_the_piece() {
+        let board = grid![
+            [. . . #],
+            [# . . .],
+            [# # # #],
+        ];
+        let piece = grid![
+            [. . . .],
+            [# . . .],
+            [. . . .],
+        ];
+        assert_eq!(board.drop_checked(piece), Err(DropError::SpawnBlocked));
+    }
+
+    #[test]
+    fn drop_with_no_solution() {
+        assert_eq!(grid!([#]).drop(grid!([#])), None)
+    }
+
+    #[test]
+    fn try_drop_blocked() {
+        assert_eq!(grid!([#]).try_drop(grid!([#])), DropOutcome::Blocked)
+    }
+
+    #[test]
+    fn drop_onto_another_block() {
+        assert_eq!(
+            grid![
+                [.],
+                [.],
+                [#],
+            ]
+            .drop(grid![
+                [#],
+                [.],
+                [.],
+            ]),
+            Some(grid![
+                [.],
+                [#],
+                [#],
+            ])
+        )
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn landing_contacts_on_another_block() {
+        let board = grid![
+            [.],
+            [.],
+            [#],
+        ];
+        let landed = board.ghost(grid![[#], [.], [.]]).unwrap();
+        assert_eq!(board.landing_contacts(&landed), [(1, 0)]);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn landing_contacts_on_floor_and_overhang() {
+        let board = grid![
+            [. . .],
+            [. . .],
+            [. # .],
+        ];
+        let landed = grid![
+            [. . .],
+            [# # #],
+            [# . .],
+        ];
+        assert_eq!(board.landing_contacts(&landed), [(1, 1), (2, 0)]);
+    }
+
+    #[test]
+    fn drop_onto_another_block_with_overhang() {
+        assert_eq!(
+            grid![
+                [. .],
+                [. .],
+                [. #],
+            ]
+            .drop(grid![
+                [# #],
+                [. .],
+                [. .],
+            ]),
+            Some(grid![
+                [. .],
+                [# #],
+                [. #],
+            ])
+        )
+    }
+
+    #[test]
+    fn drop_does_not_warp_past_overhang() {
+        assert_eq!(
+            grid![
+                [. .],
+                [# #],
+                [. .],
+            ]
+            .drop(grid![
+                [# #],
+                [. .],
+                [. .],
+            ]),
+            Some(grid![
+                [# #],
+                [# #],
+                [. .],
+            ])
+        )
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn drop_frames_through_air() {
+        let board = grid!([.], [.], [.], [.]);
+        let piece = grid!([#], [.], [.], [.]);
+        let frames = board.drop_frames(piece);
+        assert_eq!(frames.len(), 4);
+        assert_eq!(frames.last().copied(), board.drop(piece));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn drop_frames_with_no_solution() {
+        assert_eq!(grid!([#]).drop_frames(grid!([#])), vec![])
+    }
+
+    #[test]
+    fn garbage_raises_stack() {
+        use CellState::{Occupied as X, Unoccupied as O};
+        assert_eq!(
+            grid![
+                [. . .],
+                [. . .],
+                [# # .],
+            ]
+            .push_garbage([[O, X, X]]),
+            Some(grid![
+                [. . .],
+                [# # .],
+                [. # #],
+            ])
+        )
+    }
+
+    #[test]
+    fn garbage_overflow() {
+        use CellState::{Occupied as X, Unoccupied as O};
+        assert_eq!(
+            grid![
+                [# . .],
+                [# . .],
+            ]
+            .push_garbage([[O, X, X]]),
+            None
+        );
+        assert_eq!(grid![[.], [.]].push_garbage([[X], [X], [X]]), None);
+    }
+
+    #[test]
+    fn same_occupancy_ignores_colour() {
+        let shape = grid![
+            [# .],
+            [# #],
+        ];
+        assert!(shape.same_occupancy(&Grid {
+            rows: [[3u8, 0], [1, 2]]
+        }));
+        assert!(!shape.same_occupancy(&Grid {
+            rows: [[3u8, 0], [0, 2]]
+        }));
+    }
+
+    #[test]
+    fn simulate_tetris() {
+        let board = grid![
+            [. . . . .],
+            [. . . . .],
+            [. . . . .],
+            [. . . . .],
+            [# # # . #],
+            [# # # . #],
+            [# # # . #],
+            [# # # . #],
+        ];
+        let vertical_i = grid![
+            [. . . # .],
+            [. . . # .],
+            [. . . # .],
+            [. . . # .],
+            [. . . . .],
+            [. . . . .],
+            [. . . . .],
+            [. . . . .],
+        ];
+        assert_eq!(board.simulate_clears(vertical_i), Some(4));
+        let horizontal_i = grid![
+            [# # # # .],
+            [. . . . .],
+            [. . . . .],
+            [. . . . .],
+            [. . . . .],
+            [. . . . .],
+            [. . . . .],
+            [. . . . .],
+        ];
+        // lands on top of the stack, filling no rows
+        assert_eq!(board.simulate_clears(horizontal_i), Some(0));
+        assert_eq!(board.simulate_clears(board), None);
+    }
+
+    #[test]
+    fn zip_with_difference() {
+        let before = grid![
+            [. # .],
+            [# # #],
+        ];
+        let after = grid![
+            [. . .],
+            [# # .],
+        ];
+        assert_eq!(
+            before.zip_with(after, |before, after| match before == after {
+                true => CellState::Unoccupied,
+                false => CellState::Occupied,
+            }),
+            grid![
+                [. # .],
+                [. . #],
+            ]
+        )
+    }
+
+    #[test]
+    fn shift_right_empty() {
+        let _: Grid<0, 0, CellState> = grid![].shr(1);
+    }
+
+    #[test]
+    fn shift_right_clears_single_column() {
+        assert_eq!(grid![[#]].shr(1), grid![[.]]);
+    }
+
+    #[test]
+    fn shift_right_pushes_shifted_column_off_edge() {
+        assert_eq!(grid![[# #]].shr(1), grid![[. #]])
+    }
+
+    #[test]
+    fn solid_row_is_cleared() {
+        assert_eq!(
+            grid![
+                [# . .],
+                [# # #],
+                [. # .],
+            ]
+            .with_solid_rows_cleared(),
+            grid![
+                [. . .],
+                [# . .],
+                [. # .],
+            ]
+        )
+    }
+
+    #[test]
+    fn multiple_solid_rows_cleared() {
+        assert_eq!(
+            grid![
+                [# . .],
+                [# # #],
+                [# # #],
+                [. # .],
+                [# # #],
+                [. . #],
+            ]
+            .with_solid_rows_cleared(),
+            grid![
+                [. . .],
+                [. . .],
+                [. . .],
+                [# . .],
+                [. # .],
+                [. . #],
+            ]
+        )
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn multiple_solid_rows_extracted() {
+        let mut grid = grid![
+            [# . .],
+            [# # #],
+            [# # #],
+            [. # .],
+            [# # #],
+            [. . #],
+        ];
+        assert_eq!(
+            grid.extract_full_rows(),
+            [[CellState::Occupied; 3]; 3].to_vec()
+        );
+        assert_eq!(
+            grid,
+            grid![
+                [. . .],
+                [. . .],
+                [. . .],
+                [# . .],
+                [. # .],
+                [. . #],
+            ]
+        );
     }
 
     #[test]
-    fn bitand() {
-        assert_eq!(grid![[#]].bitand(grid![[.]]), Ok(grid![[#]]));
+    fn many_solid_rows_cleared_on_a_tall_grid() {
+        // quadratic clearing would make this crawl
+        const HEIGHT: usize = 10_000;
+        let mut grid = Grid::<2, HEIGHT>::default();
+        for row_ix in (0..HEIGHT).step_by(2) {
+            grid.rows[row_ix] = [CellState::Occupied; 2];
+            grid.rows[row_ix + 1] = [CellState::Occupied, CellState::Unoccupied];
+        }
+        assert_eq!(grid.clear_solid_rows(), HEIGHT / 2);
+        for (row_ix, row) in grid.rows.iter().enumerate() {
+            match row_ix < HEIGHT / 2 {
+                true => assert_eq!(row, &[CellState::Unoccupied; 2]),
+                false => assert_eq!(row, &[CellState::Occupied, CellState::Unoccupied]),
+            }
+        }
     }
 
     #[test]
-    fn cant_shift_off_edge() {
-        assert_eq!(grid![[#]].try_shift_down(1), None)
+    fn clear_row_leaves_neighbours() {
+        let mut grid = grid![
+            [# # #],
+            [# # #],
+            [# # #],
+        ];
+        grid.clear_row(1);
+        assert_eq!(
+            grid,
+            grid![
+                [# # #],
+                [. . .],
+                [# # #],
+            ]
+        )
     }
 
     #[test]
-    fn clobbering() {
+    fn clear_column_leaves_neighbours() {
+        let mut grid = grid![
+            [# # #],
+            [# # #],
+        ];
+        grid.clear_column(1);
         assert_eq!(
+            grid,
             grid![
-                [. . . .], // row 0
-                [. # # .], // row 1
-                [. . . .], // row 2
+                [# . #],
+                [# . #],
             ]
-            .bitand(grid![
-                [. . . .],
-                [. . # .],
-                [. . . .],
-            ]),
-            Err(WouldClobber {
-                row_ix: 1,
-                col_ix: 2
-            })
         )
     }
 
     #[test]
-    fn drop_single() {
-        assert_eq!(grid!([.]).drop(grid!([#])), Some(grid!([#])))
+    #[should_panic]
+    fn clear_column_out_of_bounds() {
+        grid![[# #]].clear_column(2)
     }
 
     #[test]
-    fn drop_through_air() {
-        assert_eq!(
-            grid!([.], [.], [.]).drop(grid!([#], [.], [.])),
-            Some(grid!([.], [.], [#]))
-        )
+    #[should_panic]
+    fn column_is_empty_out_of_bounds() {
+        grid![[. .]].column_is_empty(2);
     }
 
     #[test]
-    fn drop_with_no_solution() {
-        assert_eq!(grid!([#]).drop(grid!([#])), None)
+    fn floor_supports_but_air_does_not() {
+        let grid = grid![
+            [. .],
+            [. .],
+            [. .],
+        ];
+        assert!(grid.is_supported(&[(2, 0), (2, 1)]));
+        assert!(!grid.is_supported(&[(1, 0)]));
+        assert!(!grid.is_supported(&[(2, 0), (0, 1)]));
     }
 
     #[test]
-    fn drop_onto_another_block() {
+    fn dissolve_top_half() {
+        let mut board = Grid::<3, 4>::full();
+        board.retain_cells(|row_ix, _, _| row_ix >= 2);
+        assert_eq!(
+            board,
+            grid![
+                [. . .],
+                [. . .],
+                [# # #],
+                [# # #],
+            ]
+        );
+    }
+
+    #[test]
+    fn floating_block_settles_to_bottom() {
         assert_eq!(
             grid![
-                [.],
                 [.],
                 [#],
+                [.],
+                [.],
             ]
-            .drop(grid![
-                [#],
+            .settle_columns(),
+            grid![
                 [.],
                 [.],
-            ]),
-            Some(grid![
                 [.],
                 [#],
-                [#],
-            ])
+            ]
         )
     }
 
     #[test]
-    fn drop_onto_another_block_with_overhang() {
+    fn settle_columns_keeps_cell_order() {
         assert_eq!(
-            grid![
-                [. .],
-                [. .],
-                [. #],
-            ]
-            .drop(grid![
-                [# #],
-                [. .],
-                [. .],
-            ]),
-            Some(grid![
-                [. .],
-                [# #],
-                [. #],
-            ])
+            Grid {
+                rows: [[1], [0], [2], [0]]
+            }
+            .settle_columns(),
+            Grid {
+                rows: [[0], [0], [1], [2]]
+            }
         )
     }
 
     #[test]
-    fn drop_does_not_warp_past_overhang() {
+    #[cfg(feature = "std")]
+    fn try_combine_all_reports_every_clobber() {
+        let block = grid![[# #], [# #]];
         assert_eq!(
-            grid![
-                [. .],
-                [# #],
-                [. .],
-            ]
-            .drop(grid![
-                [# #],
-                [. .],
-                [. .],
-            ]),
-            Some(grid![
-                [# #],
-                [# #],
-                [. .],
+            block.try_combine_all(block),
+            Err(vec![
+                WouldClobber {
+                    row_ix: 0,
+                    col_ix: 0
+                },
+                WouldClobber {
+                    row_ix: 0,
+                    col_ix: 1
+                },
+                WouldClobber {
+                    row_ix: 1,
+                    col_ix: 0
+                },
+                WouldClobber {
+                    row_ix: 1,
+                    col_ix: 1
+                },
             ])
-        )
+        );
     }
 
     #[test]
-    fn shift_right_empty() {
-        let _: Grid<0, 0, CellState> = grid![].shr(1);
+    fn and_assign_leaves_board_unchanged_on_clobber() {
+        let original = grid![
+            [. . .],
+            [# . #],
+        ];
+        let mut board = original;
+        assert_eq!(
+            board.and_assign(grid![
+                [. # .],
+                [. . #],
+            ]),
+            Err(WouldClobber {
+                row_ix: 1,
+                col_ix: 2
+            })
+        );
+        assert_eq!(board, original);
+        assert_eq!(board.and_assign(grid![[. # .], [. # .]]), Ok(()));
+        assert_eq!(board, grid![[. # .], [# # #]]);
     }
 
     #[test]
-    fn shift_right_clears_single_column() {
-        assert_eq!(grid![[#]].shr(1), grid![[.]]);
+    fn row_and_column_transitions() {
+        let grid = grid![
+            [. # .],
+            [# . #],
+        ];
+        // |.#.| has 4, |#.#| has 2
+        assert_eq!(grid.row_transitions(), 6);
+        // each column changes once before the floor, and the middle column changes again at the floor
+        assert_eq!(grid.column_transitions(), 4);
+        assert_eq!(Grid::<3, 2>::full().row_transitions(), 0);
+        assert_eq!(Grid::<3, 2>::default().column_transitions(), 3);
     }
 
     #[test]
-    fn shift_right_pushes_shifted_column_off_edge() {
-        assert_eq!(grid![[# #]].shr(1), grid![[. #]])
+    fn landing_height_of_ghost() {
+        let board = grid![
+            [. . . .],
+            [. . . .],
+            [. . . .],
+            [# # . .],
+        ];
+        let column = grid![
+            [# . . .],
+            [# . . .],
+            [# . . .],
+            [. . . .],
+        ];
+        // lands on the stack, covering heights 2 to 4
+        let landed = board.ghost(column).unwrap();
+        assert_eq!(Grid::landing_height(&landed), 3.0);
+        assert_eq!(Grid::<4, 4>::landing_height(&Grid::default()), 0.0);
     }
 
     #[test]
-    fn solid_row_is_cleared() {
+    fn one_row_eroding_two_cells() {
+        let board = grid![
+            [. . . .],
+            [. . . .],
+            [# # . .],
+        ];
+        let piece = grid![
+            [. . . .],
+            [. . # .],
+            [. . # #],
+        ];
+        let before_clear = (board & piece).unwrap();
+        assert_eq!(Grid::eroded_cells(&before_clear, &piece), 2); // 1 row × 2 cells
+        assert_eq!(Grid::eroded_cells(&board, &Grid::default()), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn overhang_shelters_hole() {
+        let board = grid![
+            [. . . .],
+            [. # # .],
+            [# . # #],
+        ];
+        assert_eq!(board.overhang_cells(), [(1, 1)]);
+        assert_eq!(board.holes(), 1);
+        assert_eq!(Grid::<4, 3>::full().overhang_cells(), []);
+    }
+
+    #[test]
+    fn deepest_well_at_the_edge() {
+        let board = grid![
+            [. . . . . . . . . .],
+            [. # . . . . . . . .],
+            [# # # # # # # # # .],
+            [# # # # # # # # # .],
+            [# # # # # # # # # .],
+            [# # # # # # # # # .],
+        ];
+        assert_eq!(board.deepest_well(), Some((9, 4)));
+        assert_eq!(Grid::<10, 6>::default().deepest_well(), None);
+    }
+
+    #[test]
+    fn place_clipped_off_the_top() {
+        let t = grid![
+            [# # # .],
+            [. # . .],
+            [. . . .],
+        ];
         assert_eq!(
+            Grid::default().place_clipped(t, -1, 1),
             grid![
-                [# . .],
-                [# # #],
-                [. # .],
+                [. . # .],
+                [. . . .],
+                [. . . .],
             ]
-            .with_solid_rows_cleared(),
+        );
+    }
+
+    #[test]
+    fn rotate_board_round_trip() {
+        let grid = grid![
+            [# . .],
+            [. # .],
+            [. . #],
+        ];
+        assert_eq!(
+            grid.rotate_board_down(1),
             grid![
-                [. . .],
+                [. . #],
                 [# . .],
                 [. # .],
             ]
-        )
+        );
+        assert_eq!(grid.rotate_board_down(4).rotate_board_up(1), grid);
+        assert_eq!(
+            Grid::<0, 0>::default().rotate_rows(1).rotate_board_up(1),
+            Grid::default()
+        );
     }
 
     #[test]
-    fn multiple_solid_rows_cleared() {
+    fn bools_round_trip() {
+        let bools = [[true, false, false], [false, true, true]];
+        let grid = Grid::from(bools);
         assert_eq!(
+            grid,
             grid![
                 [# . .],
-                [# # #],
-                [# # #],
-                [. # .],
-                [# # #],
-                [. . #],
+                [. # #],
             ]
-            .with_solid_rows_cleared(),
+        );
+        assert_eq!(<[[bool; 3]; 2]>::from(grid), bools);
+    }
+
+    proptest! {
+        #[test]
+        fn bools_round_trip_any(bools in any::<[[bool; 5]; 4]>()) {
+            prop_assert_eq!(<[[bool; 5]; 4]>::from(Grid::from(bools)), bools);
+        }
+    }
+
+    #[test]
+    fn grow_keeps_bottom_left() {
+        let grid = grid![
+            [# .],
+            [# #],
+        ];
+        assert_eq!(
+            grid.resize::<4, 4>(),
             grid![
-                [. . .],
-                [. . .],
-                [. . .],
-                [# . .],
-                [. # .],
+                [. . . .],
+                [. . . .],
+                [# . . .],
+                [# # . .],
+            ]
+        );
+        assert_eq!(grid.resize::<4, 4>().resize::<2, 2>(), grid);
+    }
+
+    #[test]
+    fn hash_set_of_grids() {
+        let mut seen = std::collections::HashSet::new();
+        assert!(seen.insert(grid![[# .], [# #]]));
+        assert!(seen.insert(grid![[. #], [# #]]));
+        assert!(!seen.insert(grid![[# .], [# #]]), "duplicate");
+        assert_eq!(seen.len(), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn two_separate_blocks() {
+        let grid = grid![
+            [# # . . .],
+            [# # . . .],
+            [. . . # .],
+            [. . # # #],
+        ];
+        assert_eq!(
+            grid.connected_components(),
+            [
+                vec![(0, 0), (0, 1), (1, 0), (1, 1)],
+                vec![(2, 3), (3, 2), (3, 3), (3, 4)],
+            ]
+        );
+        assert_eq!(Grid::<3, 3>::default().connected_components().len(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn cascade_after_overhang_clear() {
+        let grid = grid![
+            [. # # .],
+            [. # . .],
+            [# # # #],
+            [# . . #],
+        ];
+        assert_eq!(
+            grid.with_cascade_clears(),
+            grid![
+                [. . . .],
+                [. . . .],
+                [. # # .],
+                [# # . #],
+            ]
+        );
+        // the floating piece completes a row as it falls, which clears too
+        let chain = grid![
+            [. # # .],
+            [# # # #],
+            [# . . #],
+            [# . # #],
+        ];
+        assert_eq!(
+            chain.with_cascade_clears(),
+            grid![
+                [. . . .],
+                [. . . .],
+                [. . . .],
+                [# . # #],
+            ]
+        );
+    }
+
+    #[test]
+    fn fill_rect_only_changes_region() {
+        let mut grid = Grid::<4, 3>::default();
+        grid.fill_rect(1, 1, 2, 2, CellState::Occupied);
+        assert_eq!(
+            grid,
+            grid![
+                [. . . .],
+                [. # # .],
+                [. # # .],
+            ]
+        );
+    }
+
+    #[test]
+    fn toggle_every_cell() {
+        let mut grid = grid![
+            [. # .],
+            [# # .],
+        ];
+        grid.for_each_cell_mut(|_, _, cell| {
+            *cell = match cell {
+                CellState::Unoccupied => CellState::Occupied,
+                CellState::Occupied => CellState::Unoccupied,
+            }
+        });
+        assert_eq!(
+            grid,
+            grid![
+                [# . #],
                 [. . #],
             ]
-        )
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn fill_rect_out_of_bounds() {
+        Grid::<4, 3>::default().fill_rect(2, 0, 2, 1, CellState::Occupied)
+    }
+
+    #[test]
+    fn rotational_symmetry() {
+        assert!(grid![[# #], [# #]].is_symmetric_under_rotation());
+        assert!(tetromino::<4, 4>(BlockShape::Q).is_symmetric_under_rotation());
+        assert!(!tetromino::<4, 4>(BlockShape::T).is_symmetric_under_rotation());
+        assert!(Grid::<4, 4>::default().is_symmetric_under_rotation());
+    }
+
+    #[test]
+    fn settle_left() {
+        assert_eq!(grid![[. # . #]].settle_left(), grid![[# # . .]])
+    }
+
+    #[test]
+    fn settle_sideways_keeps_cell_order() {
+        let grid = Grid {
+            rows: [[0, 1, 0, 2, 0]],
+        };
+        assert_eq!(grid.settle_left().rows, [[1, 2, 0, 0, 0]]);
+        assert_eq!(grid.settle_right().rows, [[0, 0, 0, 1, 2]]);
+    }
+
+    #[test]
+    fn shapes() {
+        use BlockShape::{I, J, L, Q, S, T, Z};
+        assert_eq!(
+            tetromino(I),
+            grid![
+                [# # # # .],
+                [. . . . .],
+                [. . . . .],
+            ]
+        );
+        assert_eq!(
+            tetromino(J),
+            grid![
+                [. # . .],
+                [. # . .],
+                [# # . .],
+                [. . . .],
+            ]
+        );
+        assert_eq!(
+            tetromino(L),
+            grid![
+                [# . . .],
+                [# . . .],
+                [# # . .],
+                [. . . .],
+            ]
+        );
+        assert_eq!(
+            tetromino(Q),
+            grid![
+                [# # . .],
+                [# # . .],
+                [. . . .],
+            ]
+        );
+        assert_eq!(
+            tetromino(S),
+            grid![
+                [. # # .],
+                [# # . .],
+                [. . . .],
+            ]
+        );
+        assert_eq!(
+            tetromino(T),
+            grid![
+                [# # # .],
+                [. # . .],
+                [. . . .],
+            ]
+        );
+        assert_eq!(
+            tetromino(Z),
+            grid![
+                [# # . .],
+                [. # # .],
+                [. . . .],
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn distinct_rotations() {
+        use BlockShape::{I, Q, T};
+        let count = |shape| tetromino::<4, 4>(shape).distinct_rotations().len();
+        assert_eq!(count(Q), 1);
+        assert_eq!(count(I), 2);
+        assert_eq!(count(T), 4);
     }
 
     #[test]
@@ -586,4 +3640,65 @@ mod tests {
             ],
         )
     }
+
+    #[test]
+    fn non_copy_cells() {
+        let label = |s: &str| s.to_string();
+        let board = Grid {
+            rows: [[String::new(), String::new()], [label("a"), String::new()]],
+        };
+        let piece = Grid {
+            rows: [[String::new(), label("b")], [String::new(), String::new()]],
+        };
+        let mut landed = board.drop(piece).unwrap();
+        assert_eq!(
+            landed.rows,
+            [[String::new(), String::new()], [label("a"), label("b")]]
+        );
+        assert_eq!(landed.clear_solid_rows(), 1);
+        assert_eq!(landed, Grid::default());
+        assert_eq!(landed.try_bump_down(), Some(Grid::default()));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn vec_of_rows_is_validated() {
+        let (o, e) = (CellState::Occupied, CellState::Unoccupied);
+        assert_eq!(
+            Grid::<2, 2>::try_from(vec![vec![e, e], vec![o, e]]),
+            Ok(grid![[. .], [# .]])
+        );
+        assert_eq!(
+            Grid::<2, 2>::try_from(vec![vec![e, e]]),
+            Err(DimensionMismatch::Height {
+                expected: 2,
+                actual: 1
+            })
+        );
+        assert_eq!(
+            Grid::<2, 2>::try_from(vec![vec![e, e], vec![o, e, o]]),
+            Err(DimensionMismatch::Width {
+                row_ix: 1,
+                expected: 2,
+                actual: 3
+            })
+        );
+        grid![[. #]].debug_assert_well_formed();
+    }
+
+    #[test]
+    fn cells_without_default() {
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        enum Suit {
+            Hearts,
+            Spades,
+        }
+        let grid = Grid::<2, 2, char>::filled_with('x');
+        assert_eq!(grid.rows, [['x', 'x'], ['x', 'x']]);
+
+        let mut suits = Grid::<2, 1, _>::filled_with(Suit::Hearts);
+        suits.rows[0][1] = Suit::Spades;
+        assert_eq!(suits.rotate_cw().rows, [[Suit::Hearts], [Suit::Spades]]);
+        assert_eq!(suits.transpose().transpose(), suits);
+    }
 }
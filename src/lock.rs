@@ -0,0 +1,89 @@
+//! Lock delay, the grace period before a piece resting on the stack locks in place.
+
+/// A countdown, in frames, until a resting piece locks.
+///
+/// Moving or rotating the piece (see [LockDelay::on_move]) restarts the countdown,
+/// but only `reset_limit` times, so that a player can't stall forever.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockDelay {
+    delay: u32,
+    frames_remaining: u32,
+    reset_limit: u32,
+    resets: u32,
+}
+
+impl LockDelay {
+    /// A piece will lock after `delay` frames, restarting the countdown at most `reset_limit` times.
+    pub fn new(delay: u32, reset_limit: u32) -> Self {
+        Self {
+            delay,
+            frames_remaining: delay,
+            reset_limit,
+            resets: 0,
+        }
+    }
+
+    /// Advance one frame.
+    pub fn tick(&mut self) {
+        self.frames_remaining = self.frames_remaining.saturating_sub(1)
+    }
+
+    /// The piece has moved, restart the countdown if any resets remain.
+    /// Returns whether the countdown was restarted.
+    /// ```
+    /// use tetris::lock::LockDelay;
+    /// let mut lock = LockDelay::new(2, 1);
+    /// lock.tick();
+    /// assert!(lock.on_move());
+    /// lock.tick();
+    /// assert!(!lock.is_expired());
+    /// ```
+    pub fn on_move(&mut self) -> bool {
+        if self.resets >= self.reset_limit || self.is_expired() {
+            return false;
+        }
+        self.resets += 1;
+        self.frames_remaining = self.delay;
+        true
+    }
+
+    /// Whether the piece should lock.
+    pub fn is_expired(&self) -> bool {
+        self.frames_remaining == 0
+    }
+
+    pub fn frames_remaining(&self) -> u32 {
+        self.frames_remaining
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expires_after_delay() {
+        let mut lock = LockDelay::new(3, 0);
+        lock.tick();
+        lock.tick();
+        assert!(!lock.is_expired());
+        lock.tick();
+        assert!(lock.is_expired());
+    }
+
+    #[test]
+    fn resets_are_limited() {
+        let mut lock = LockDelay::new(2, 2);
+        for _ in 0..2 {
+            lock.tick();
+            assert!(lock.on_move());
+            assert_eq!(lock.frames_remaining(), 2);
+        }
+        lock.tick();
+        // out of resets - moving no longer buys time
+        assert!(!lock.on_move());
+        assert_eq!(lock.frames_remaining(), 1);
+        lock.tick();
+        assert!(lock.is_expired());
+    }
+}
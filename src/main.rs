@@ -1,23 +1,16 @@
 use anyhow::Context;
 use clap::Parser;
-use derive_more::From;
 use indoc::indoc;
-use recap::Recap;
-use serde::Deserialize;
 use std::{
     fs::File,
     io::{self, BufRead, BufReader, Write},
-    ops::Shr,
     path::{Path, PathBuf},
-    str::FromStr,
 };
-use strum::EnumString;
-use tetris::{is_occupied, CellState, Grid};
-
-/// From brief
-const WIDTH: usize = 10;
-/// From brief, with allowance for the tallest block
-const HEIGHT: usize = 100 + 3;
+use tetris::{
+    play::{play_lines, process_blocks_with, trace_line, InputBlock},
+    play::{HEIGHT, WIDTH},
+    BlockShape, Grid,
+};
 
 // todo: add tracing etc
 #[derive(Debug, Parser)]
@@ -40,27 +33,158 @@ struct Args {
     /// The output file (defaults to stdout)
     #[arg(short, long)]
     outfile: Option<PathBuf>,
+    /// Print the height after each block, comma-separated, instead of just the final height
+    #[arg(long)]
+    trace_heights: bool,
+    /// Instead of processing INFILE, replay a log of one `SHAPE,COLUMN` placement per line,
+    /// drawing the grid after each placement
+    #[arg(long, value_name = "FILE")]
+    replay: Option<PathBuf>,
+    /// When replaying, draw each block in an ANSI colour for its shape
+    #[arg(long, requires = "replay")]
+    color: bool,
+    /// Process up to N lines at once. Output is in the same order as the input
+    #[cfg(feature = "rayon")]
+    #[arg(long, value_name = "N", conflicts_with_all = ["trace_heights", "replay"])]
+    jobs: Option<usize>,
+    /// Instead of processing INFILE, drop random pieces wherever they leave the stack lowest,
+    /// then draw the final grid and print its height
+    #[cfg(feature = "rand")]
+    #[arg(long, conflicts_with_all = ["trace_heights", "replay"])]
+    self_play: bool,
+    /// Seed for --self-play, so that games can be reproduced (defaults to a random seed)
+    #[cfg(feature = "rand")]
+    #[arg(long, value_name = "N", requires = "self_play")]
+    seed: Option<u64>,
+    /// How many pieces to drop in --self-play
+    #[cfg(feature = "rand")]
+    #[arg(long, value_name = "K", default_value_t = 100, requires = "self_play")]
+    pieces: usize,
 }
 
 fn main() -> anyhow::Result<()> {
     let args = Args::parse();
     let infile = or_stdin(args.infile)?;
     let mut outfile = or_stdout(args.outfile)?;
-    for line in infile.lines() {
-        let input_blocks =
-            parse_line(&line.context("couldn't read input")?).context("couldn't parse line")?;
-        writeln!(
-            outfile,
-            "{}",
-            highest_block_after_processing(Grid::<WIDTH, HEIGHT>::default(), input_blocks)
-                .context("couldn't place input block on congested grid")?
-        )
-        .context("couldn't write output")?;
+    if let Some(path) = args.replay {
+        let log = BufReader::new(File::open(path).context("couldn't open replay")?);
+        if args.color {
+            replay(
+                Grid::<WIDTH, HEIGHT, Option<BlockShape>>::default(),
+                log,
+                |frame| writeln!(outfile, "{}", colored(frame)),
+            )?;
+        } else {
+            replay(Grid::<WIDTH, HEIGHT>::default(), log, |frame| {
+                writeln!(outfile, "{frame}")
+            })?;
+        }
+        outfile.flush().context("couldn't write output")?;
+        return Ok(());
+    }
+    #[cfg(feature = "rand")]
+    if args.self_play {
+        let seed = args
+            .seed
+            .unwrap_or_else(|| rand::Rng::gen(&mut rand::thread_rng()));
+        let grid = self_play(seed, args.pieces)?;
+        write!(outfile, "{grid}").context("couldn't write output")?;
+        writeln!(outfile, "{}", tetris::play::highest_block(&grid))
+            .context("couldn't write output")?;
+        outfile.flush().context("couldn't write output")?;
+        return Ok(());
+    }
+    if args.trace_heights {
+        for line in game_lines(infile) {
+            let line = line.context("couldn't read input")?;
+            let heights = trace_line(&line).context("couldn't process line")?;
+            writeln!(
+                outfile,
+                "{}",
+                heights
+                    .iter()
+                    .map(usize::to_string)
+                    .collect::<Vec<_>>()
+                    .join(",")
+            )
+            .context("couldn't write output")?;
+        }
+    } else {
+        #[cfg(feature = "rayon")]
+        if let Some(jobs) = args.jobs {
+            for height in play_lines_parallel(infile, jobs)? {
+                writeln!(outfile, "{}", height.context("couldn't process line")?)
+                    .context("couldn't write output")?;
+            }
+            outfile.flush().context("couldn't write output")?;
+            return Ok(());
+        }
+        let mut read_error = None;
+        let lines =
+            game_lines(infile).map_while(|line| line.map_err(|e| read_error = Some(e)).ok());
+        for height in play_lines(lines) {
+            writeln!(outfile, "{}", height.context("couldn't process line")?)
+                .context("couldn't write output")?;
+        }
+        if let Some(e) = read_error {
+            return Err(e).context("couldn't read input");
+        }
     }
     outfile.flush().context("couldn't write output")?;
     Ok(())
 }
 
+/// The lines of `infile`, skipping blank lines and `#` comments
+fn game_lines(infile: impl BufRead) -> impl Iterator<Item = io::Result<String>> {
+    infile.lines().filter(|line| {
+        line.as_ref().map_or(true, |line| {
+            let line = line.trim();
+            !line.is_empty() && !line.starts_with('#')
+        })
+    })
+}
+
+/// Like [play_lines], but on a pool of `jobs` threads
+#[cfg(feature = "rayon")]
+fn play_lines_parallel(
+    infile: impl BufRead,
+    jobs: usize,
+) -> anyhow::Result<Vec<anyhow::Result<usize>>> {
+    use rayon::prelude::*;
+    use tetris::play::play_line;
+    let lines = game_lines(infile)
+        .collect::<Result<Vec<_>, _>>()
+        .context("couldn't read input")?;
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(jobs)
+        .build()
+        .context("couldn't start worker threads")?;
+    Ok(pool.install(|| lines.par_iter().map(|line| play_line(line)).collect()))
+}
+
+/// Drop `pieces` pieces from a [SevenBag](tetris::random::SevenBag) seeded with `seed`,
+/// each in the placement which leaves the lowest [aggregate height](Grid::aggregate_height) once rows have cleared
+#[cfg(feature = "rand")]
+fn self_play(seed: u64, pieces: usize) -> anyhow::Result<Grid<WIDTH, HEIGHT>> {
+    use rand::{rngs::StdRng, SeedableRng};
+    use tetris::random::SevenBag;
+    let mut grid = Grid::default();
+    for (placed, shape) in SevenBag::new(StdRng::seed_from_u64(seed))
+        .take(pieces)
+        .enumerate()
+    {
+        grid = grid
+            .legal_placements(shape)
+            .into_iter()
+            .map(|(_, _, landed)| landed.with_solid_rows_cleared())
+            .min_by_key(Grid::aggregate_height)
+            .with_context(|| {
+                format!("game over after {placed} pieces: nowhere to put {shape:?}")
+            })?;
+    }
+    Ok(grid)
+}
+
 fn or_stdin(path: Option<impl AsRef<Path>>) -> anyhow::Result<Box<dyn BufRead>> {
     if let Some(path) = path {
         match File::open(path) {
@@ -81,262 +205,158 @@ fn or_stdout(path: Option<impl AsRef<Path>>) -> anyhow::Result<Box<dyn Write>> {
         Ok(Box::new(io::stdout()))
     }
 }
-/// drop each [InputBlock] onto a [Grid], and clear rows, returning the final state of the grid
-fn process_blocks<const WIDTH: usize, const HEIGHT: usize>(
-    mut grid: Grid<WIDTH, HEIGHT>,
-    blocks: impl IntoIterator<Item = impl Into<InputBlock>>,
-) -> anyhow::Result<Grid<WIDTH, HEIGHT>> {
-    for block in blocks {
-        let InputBlock {
-            shape,
-            starting_column,
-        } = block.into();
-        let new_shape = grid_for(shape).shr(starting_column);
-        grid = grid
-            .drop(new_shape)
-            .context("grid's top row are already occupied")?
-            .with_solid_rows_cleared();
-    }
-    Ok(grid)
+/// Parse a `SHAPE,COLUMN` line from a replay log
+fn parse_replay_line(line: &str) -> anyhow::Result<InputBlock> {
+    let (shape, column) = line
+        .trim()
+        .split_once(',')
+        .with_context(|| format!("expected SHAPE,COLUMN, got {line:?}"))?;
+    Ok(InputBlock {
+        shape: shape
+            .trim()
+            .parse()
+            .with_context(|| format!("couldn't parse shape {shape:?}"))?,
+        starting_column: column
+            .trim()
+            .parse()
+            .with_context(|| format!("couldn't parse column {column:?}"))?,
+    })
 }
 
-fn first_occupied_row_ix<const WIDTH: usize, const HEIGHT: usize>(
-    grid: &Grid<WIDTH, HEIGHT>,
-) -> Option<usize> {
-    (0..HEIGHT).find(|&row_ix| grid.rows[row_ix].iter().any(is_occupied))
-}
-fn highest_block<const WIDTH: usize, const HEIGHT: usize>(grid: &Grid<WIDTH, HEIGHT>) -> usize {
-    first_occupied_row_ix(grid)
-        .map(|row_ix| HEIGHT - row_ix)
-        .unwrap_or(0)
-}
-
-fn highest_block_after_processing<const WIDTH: usize, const HEIGHT: usize>(
-    grid: Grid<WIDTH, HEIGHT>,
-    blocks: impl IntoIterator<Item = impl Into<InputBlock>>,
-) -> anyhow::Result<usize> {
-    let final_grid = process_blocks(grid, blocks)?;
-    Ok(highest_block(&final_grid))
-}
-
-#[derive(Debug, EnumString, Deserialize, PartialEq, Eq, Clone, Copy)]
-enum BlockShape {
-    Q,
-    Z,
-    S,
-    T,
-    I,
-    L,
-    J,
-}
-
-#[derive(Debug, Deserialize, Recap, PartialEq, Eq, Clone, Copy, From)]
-#[recap(regex = r#"(?P<shape>\w)(?P<starting_column>\d+)"#)]
-struct InputBlock {
-    pub shape: BlockShape,
-    pub starting_column: usize,
+/// Call `on_frame` with the grid after each placement in a replay log, as it's played.
+/// After `on_frame` fails, the rest of the log is still checked, but not drawn.
+fn replay<const WIDTH: usize, const HEIGHT: usize, CellT>(
+    grid: Grid<WIDTH, HEIGHT, CellT>,
+    log: impl BufRead,
+    mut on_frame: impl FnMut(&Grid<WIDTH, HEIGHT, CellT>) -> io::Result<()>,
+) -> anyhow::Result<()>
+where
+    CellT: Default + Clone + PartialEq + From<BlockShape>,
+{
+    let placements = log
+        .lines()
+        .map(|line| parse_replay_line(&line.context("couldn't read replay")?));
+    let mut written = Ok(());
+    process_blocks_with(grid, placements, |grid| {
+        if written.is_ok() {
+            written = on_frame(grid)
+        }
+    })?;
+    written.context("couldn't write output")
 }
 
-// todo: make a grammar and use a parser
-fn parse_line(s: &str) -> anyhow::Result<Vec<InputBlock>> {
-    Ok(s.trim()
-        .split(',')
-        .map(InputBlock::from_str)
-        .collect::<Result<Vec<_>, _>>()?)
-}
+const ANSI_RESET: &str = "\x1b[0m";
 
-/// Place `with` in each of the `coords`
-/// # Panics
-/// - If any of the coords are out of bounds
-fn fill<const WIDTH: usize, const HEIGHT: usize, CellT: Clone>(
-    grid: &mut Grid<WIDTH, HEIGHT, CellT>,
-    with: CellT,
-    coords: impl IntoIterator<Item = (usize, usize)>,
-) {
-    for (row_ix, col_ix) in coords {
-        grid.rows[row_ix][col_ix] = with.clone();
+/// ANSI background colour for each shape, following the guideline colours
+fn ansi_background(shape: BlockShape) -> &'static str {
+    match shape {
+        BlockShape::I => "\x1b[46m",
+        BlockShape::Q => "\x1b[43m",
+        BlockShape::T => "\x1b[45m",
+        BlockShape::S => "\x1b[42m",
+        BlockShape::Z => "\x1b[41m",
+        BlockShape::J => "\x1b[44m",
+        BlockShape::L => "\x1b[48;5;208m",
     }
 }
 
-/// Place a [BlockShape] in a new [Grid]
-/// # Panics
-/// - If the grid is too small to fit the shape
-fn grid_for<const WIDTH: usize, const HEIGHT: usize>(shape: BlockShape) -> Grid<WIDTH, HEIGHT> {
-    use CellState::Occupied as X;
-    // once const rust is more mature, we can static assert that WIDTH fits I and HEIGHT fits J/L
-    // (the code will currently panic)
-    let mut grid = Grid::default();
-    match shape {
-        BlockShape::Q => fill(&mut grid, X, [(0, 0), (0, 1), (1, 0), (1, 1)]),
-        BlockShape::Z => fill(&mut grid, X, [(0, 0), (0, 1), (1, 1), (1, 2)]),
-        BlockShape::S => fill(&mut grid, X, [(0, 1), (0, 2), (1, 0), (1, 1)]),
-        BlockShape::T => fill(&mut grid, X, [(0, 0), (0, 1), (0, 2), (1, 1)]),
-        BlockShape::I => fill(&mut grid, X, [(0, 0), (0, 1), (0, 2), (0, 3)]),
-        BlockShape::L => fill(&mut grid, X, [(0, 0), (1, 0), (2, 0), (2, 1)]),
-        BlockShape::J => fill(&mut grid, X, [(0, 1), (1, 1), (2, 1), (2, 0)]),
+/// Like [Grid]'s [Display](std::fmt::Display), but each occupied cell is coloured by the shape that filled it
+fn colored<const WIDTH: usize, const HEIGHT: usize>(
+    grid: &Grid<WIDTH, HEIGHT, Option<BlockShape>>,
+) -> String {
+    let mut s = String::new();
+    for row in grid.iter_rows() {
+        for cell in row {
+            match cell {
+                Some(shape) => {
+                    s.push_str(ansi_background(*shape));
+                    s.push('#');
+                    s.push_str(ANSI_RESET)
+                }
+                None => s.push('.'),
+            }
+        }
+        s.push('\n')
     }
-    grid
+    s
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use tetris::grid;
-    use BlockShape::{I, J, L, Q, S, T, Z};
 
     #[test]
-    fn parse1() -> anyhow::Result<()> {
-        use BlockShape::{I, Q};
-        assert_eq!(
-            parse_line("I0,I4,Q8")?,
-            vec![
-                InputBlock::from((I, 0)),
-                InputBlock::from((I, 4)),
-                InputBlock::from((Q, 8))
-            ]
-        );
-        Ok(())
-    }
-    #[test]
-    fn shapes() -> anyhow::Result<()> {
-        assert_eq!(
-            grid_for(I),
-            grid![
-                [# # # # .],
-                [. . . . .]
-            ]
-        );
-        assert_eq!(
-            grid_for(J),
-            grid![
-                [. # .],
-                [. # .],
-                [# # .],
-                [. . .],
-            ]
-        );
-        assert_eq!(
-            grid_for(L),
-            grid![
-                [# . .],
-                [# . .],
-                [# # .],
-                [. . .],
-            ]
-        );
-        assert_eq!(
-            grid_for(Q),
-            grid![
-                [# # .],
-                [# # .],
-                [. . .],
-            ]
-        );
-        assert_eq!(
-            grid_for(S),
-            grid![
-                [. # # .],
-                [# # . .],
-                [. . . .],
-            ]
-        );
-        assert_eq!(
-            grid_for(T),
-            grid![
-                [# # # .],
-                [. # . .],
-                [. . . .],
-            ]
-        );
-        assert_eq!(
-            grid_for(Z),
-            grid![
-                [# # . .],
-                [. # # .],
-                [. . . .],
-            ]
-        );
-        Ok(())
-    }
-
-    const EXAMPLE1: [(BlockShape, usize); 3] = [(I, 0), (I, 4), (Q, 8)];
-    const EXAMPLE2: [(BlockShape, usize); 3] = [(T, 1), (Z, 3), (I, 4)];
-    const EXAMPLE3: [(BlockShape, usize); 8] = [
-        (Q, 0),
-        (I, 2),
-        (I, 6),
-        (I, 0),
-        (I, 6),
-        (I, 6),
-        (Q, 2),
-        (Q, 4),
-    ];
+    fn comments_and_blank_lines_are_skipped() -> anyhow::Result<()> {
+        let input = indoc!(
+            "
+            # the first example
+            I0,I4,Q8
 
-    #[test]
-    fn process_example1() -> anyhow::Result<()> {
-        assert_eq!(
-            process_blocks(Grid::default(), EXAMPLE1)?,
-            grid![
-                [. . . . . . . . . . ],
-                [. . . . . . . . . . ],
-                [. . . . . . . . # # ],
-            ]
+              # indented comment
+            T1,Z3,I4
+            "
         );
-        Ok(())
-    }
-
-    #[test]
-    fn process_example2() -> anyhow::Result<()> {
+        let lines = game_lines(input.as_bytes()).collect::<io::Result<Vec<_>>>()?;
         assert_eq!(
-            process_blocks(Grid::default(), EXAMPLE2)?,
-            grid![
-                [. . . . # # # # . . ],
-                [. . . # # . . . . . ],
-                [. # # # # # . . . . ],
-                [. . # . . . . . . . ],
-            ]
+            play_lines(lines.into_iter()).collect::<anyhow::Result<Vec<_>>>()?,
+            [1, 4]
         );
         Ok(())
     }
 
     #[test]
-    fn process_example3() -> anyhow::Result<()> {
-        assert_eq!(
-            process_blocks(Grid::default(), EXAMPLE3)?,
-            grid![
-                [. . . . . . . . . .],
-                [. . . . . . . . . .],
-                [. . # # . . . . . .],
-                [. . # # . . . . . .],
-                [# # . . # # # # # #],
-            ]
+    fn replay_short_log() -> anyhow::Result<()> {
+        let log = indoc!(
+            "
+            I,0
+            Q,4
+            T,1
+            "
         );
-        Ok(())
-    }
-    #[test]
-    fn highest_block_example1() -> anyhow::Result<()> {
+        let mut frames = Vec::new();
+        replay(Grid::<6, 4>::default(), log.as_bytes(), |frame| {
+            frames.push(*frame);
+            Ok(())
+        })?;
+        // the I and Q fill the bottom row, which clears
+        assert_eq!(frames.len(), 3);
         assert_eq!(
-            highest_block_after_processing(Grid::<WIDTH, HEIGHT>::default(), EXAMPLE1)?,
-            1
+            frames.last().unwrap().to_string(),
+            indoc!(
+                "
+                ......
+                ......
+                .###..
+                ..#.##
+                "
+            )
+            .trim_start()
         );
         Ok(())
     }
 
     #[test]
-    fn highest_block_example2() -> anyhow::Result<()> {
+    fn colored_replay() -> anyhow::Result<()> {
+        let mut out = Vec::new();
+        replay(
+            Grid::<4, 4, Option<BlockShape>>::default(),
+            "Q,0\n".as_bytes(),
+            |frame| write!(out, "{}", colored(frame)),
+        )?;
+        let yellow = "\x1b[43m#\x1b[0m";
         assert_eq!(
-            highest_block_after_processing(Grid::<WIDTH, HEIGHT>::default(), EXAMPLE2)?,
-            4
+            String::from_utf8(out)?,
+            format!("....\n....\n{yellow}{yellow}..\n{yellow}{yellow}..\n")
         );
         Ok(())
     }
 
+    #[cfg(feature = "rand")]
     #[test]
-    fn highest_block_example3() -> anyhow::Result<()> {
-        assert_eq!(
-            highest_block_after_processing(Grid::<WIDTH, HEIGHT>::default(), EXAMPLE3)?,
-            3
-        );
+    fn self_play_is_deterministic() -> anyhow::Result<()> {
+        let grid = self_play(42, 10)?;
+        assert_eq!(grid, self_play(42, 10)?);
+        assert!(!grid.is_empty_board());
         Ok(())
     }
 }
@@ -0,0 +1,393 @@
+//! The game from the brief.
+//!
+//! Each line of input is a comma-separated sequence of [InputBlock]s, which are dropped in turn onto an
+//! empty [WIDTH] × [HEIGHT] [Grid], clearing rows in typical tetris style.
+//! The result for each line is the height of the tallest occupied row.
+
+use crate::{is_occupied, BlockShape, Grid};
+use anyhow::{bail, Context};
+use derive_more::From;
+use recap::Recap;
+use serde::Deserialize;
+use std::str::FromStr;
+
+/// From brief
+pub const WIDTH: usize = 10;
+/// From brief
+pub const PLAYFIELD_HEIGHT: usize = 100;
+/// Allowance above the playfield for spawning the tallest block
+pub const SPAWN_HEIGHT: usize = 3;
+pub const HEIGHT: usize = PLAYFIELD_HEIGHT + SPAWN_HEIGHT;
+
+/// The [highest_block] after each line of comma-separated [InputBlock]s, each played on a fresh [Grid].
+/// ```
+/// use tetris::play::play_lines;
+/// let heights = play_lines(["I0,I4,Q8".to_string()].into_iter());
+/// assert_eq!(heights.map(Result::unwrap).collect::<Vec<_>>(), [1]);
+/// ```
+pub fn play_lines(
+    lines: impl Iterator<Item = String>,
+) -> impl Iterator<Item = anyhow::Result<usize>> {
+    lines.map(|line| play_line(&line))
+}
+
+/// The [highest_block] after playing a line of comma-separated [InputBlock]s on a fresh [Grid].
+/// Fails if the stack grows above the [PLAYFIELD_HEIGHT].
+pub fn play_line(line: &str) -> anyhow::Result<usize> {
+    let final_grid = process_blocks(Grid::<WIDTH, HEIGHT>::default(), parse_line(line))?;
+    ensure_within_playfield(&final_grid, PLAYFIELD_HEIGHT)?;
+    Ok(highest_block(&final_grid))
+}
+
+/// The [highest_block] after each block in a line of comma-separated [InputBlock]s, played on a fresh [Grid].
+/// Fails if the stack grows above the [PLAYFIELD_HEIGHT].
+pub fn trace_line(line: &str) -> anyhow::Result<Vec<usize>> {
+    let mut heights = Vec::new();
+    let final_grid =
+        process_blocks_with(Grid::<WIDTH, HEIGHT>::default(), parse_line(line), |grid| {
+            heights.push(highest_block(grid))
+        })?;
+    ensure_within_playfield(&final_grid, PLAYFIELD_HEIGHT)?;
+    Ok(heights)
+}
+
+/// Drop each [InputBlock] onto a [Grid] as it arrives, and clear rows, returning the final state of the grid.
+/// Stops at the first `Err` block.
+pub fn process_blocks<const WIDTH: usize, const HEIGHT: usize, CellT>(
+    grid: Grid<WIDTH, HEIGHT, CellT>,
+    blocks: impl IntoIterator<Item = anyhow::Result<impl Into<InputBlock>>>,
+) -> anyhow::Result<Grid<WIDTH, HEIGHT, CellT>>
+where
+    CellT: Default + Clone + PartialEq + From<BlockShape>,
+{
+    process_blocks_with(grid, blocks, |_| {})
+}
+
+/// As [process_blocks], calling `on_placed` with the grid after each block has landed and rows have cleared.
+///
+/// Each cell of a placed block is filled with `CellT::from(shape)`.
+pub fn process_blocks_with<const WIDTH: usize, const HEIGHT: usize, CellT>(
+    mut grid: Grid<WIDTH, HEIGHT, CellT>,
+    blocks: impl IntoIterator<Item = anyhow::Result<impl Into<InputBlock>>>,
+    mut on_placed: impl FnMut(&Grid<WIDTH, HEIGHT, CellT>),
+) -> anyhow::Result<Grid<WIDTH, HEIGHT, CellT>>
+where
+    CellT: Default + Clone + PartialEq + From<BlockShape>,
+{
+    for block in blocks {
+        let InputBlock {
+            shape,
+            starting_column,
+        } = block?.into();
+        grid = grid.play(shape, starting_column)?;
+        on_placed(&grid);
+    }
+    Ok(grid)
+}
+
+fn first_occupied_row_ix<const WIDTH: usize, const HEIGHT: usize>(
+    grid: &Grid<WIDTH, HEIGHT>,
+) -> Option<usize> {
+    (0..HEIGHT).find(|&row_ix| grid.rows[row_ix].iter().any(is_occupied))
+}
+/// The height of the tallest occupied row
+pub fn highest_block<const WIDTH: usize, const HEIGHT: usize>(grid: &Grid<WIDTH, HEIGHT>) -> usize {
+    first_occupied_row_ix(grid)
+        .map(|row_ix| HEIGHT - row_ix)
+        .unwrap_or(0)
+}
+
+/// The [highest_block] after processing every block
+pub fn highest_block_after_processing<const WIDTH: usize, const HEIGHT: usize>(
+    grid: Grid<WIDTH, HEIGHT>,
+    blocks: impl IntoIterator<Item = anyhow::Result<impl Into<InputBlock>>>,
+) -> anyhow::Result<usize> {
+    let final_grid = process_blocks(grid, blocks)?;
+    Ok(highest_block(&final_grid))
+}
+
+/// The [highest_block] after each block is placed
+pub fn heights_after_each_block<const WIDTH: usize, const HEIGHT: usize>(
+    grid: Grid<WIDTH, HEIGHT>,
+    blocks: impl IntoIterator<Item = anyhow::Result<impl Into<InputBlock>>>,
+) -> anyhow::Result<Vec<usize>> {
+    let mut heights = Vec::new();
+    process_blocks_with(grid, blocks, |grid| heights.push(highest_block(grid)))?;
+    Ok(heights)
+}
+
+/// Fail if the stack has grown above the bottom `playable_height` rows, e.g into the spawn area
+fn ensure_within_playfield<const WIDTH: usize, const HEIGHT: usize>(
+    grid: &Grid<WIDTH, HEIGHT>,
+    playable_height: usize,
+) -> anyhow::Result<()> {
+    if grid.overflowed_playfield(playable_height) {
+        bail!(
+            "stack exceeded {playable_height} rows (reached {})",
+            highest_block(grid)
+        )
+    }
+    Ok(())
+}
+
+/// A shape, and the column of its leftmost cell, e.g `Q4`
+#[derive(Debug, Deserialize, Recap, PartialEq, Eq, Clone, Copy, From)]
+#[recap(regex = r#"(?P<shape>\w)(?P<starting_column>\d+)"#)]
+pub struct InputBlock {
+    pub shape: BlockShape,
+    pub starting_column: usize,
+}
+
+// todo: make a grammar and use a parser
+/// Lazily parse each comma-separated [InputBlock], so long lines needn't be collected up front
+pub fn parse_line(s: &str) -> impl Iterator<Item = anyhow::Result<InputBlock>> + '_ {
+    s.trim().split(',').map(|token| {
+        InputBlock::from_str(token).with_context(|| format!("couldn't parse {token:?}"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid;
+    use BlockShape::{I, Q, T, Z};
+
+    #[test]
+    fn parse1() -> anyhow::Result<()> {
+        use BlockShape::{I, Q};
+        assert_eq!(
+            parse_line("I0,I4,Q8").collect::<anyhow::Result<Vec<_>>>()?,
+            vec![
+                InputBlock::from((I, 0)),
+                InputBlock::from((I, 4)),
+                InputBlock::from((Q, 8))
+            ]
+        );
+        Ok(())
+    }
+    #[test]
+    fn game_over_when_spawn_blocked() {
+        let err = process_blocks(Grid::<4, 3>::default(), [(Q, 0), (Q, 0)].map(Ok)).unwrap_err();
+        assert!(err.to_string().starts_with("game over"));
+    }
+
+    #[test]
+    fn overhanging_block_is_rejected() {
+        let err = process_blocks(Grid::<WIDTH, HEIGHT>::default(), [(I, 8)].map(Ok)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "I8 would hang off the right edge: I is 4 wide, and the grid is 10 wide"
+        );
+    }
+
+    #[test]
+    fn huge_column_is_rejected() {
+        let err = process_blocks(
+            Grid::<WIDTH, HEIGHT>::default(),
+            parse_line("I18446744073709551615"),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "I18446744073709551615 would hang off the right edge: I is 4 wide, and the grid is 10 wide"
+        );
+    }
+
+    #[test]
+    fn stack_taller_than_playfield() -> anyhow::Result<()> {
+        let err = play_line(&["Q0"; 51].join(",")).unwrap_err();
+        assert_eq!(err.to_string(), "stack exceeded 100 rows (reached 102)");
+        // only the brief's game has a playfield, so a shallow grid can be filled to the top
+        assert_eq!(
+            highest_block_after_processing(Grid::<4, 4>::default(), [(Q, 0); 2].map(Ok))?,
+            4
+        );
+        Ok(())
+    }
+
+    const EXAMPLE1: [(BlockShape, usize); 3] = [(I, 0), (I, 4), (Q, 8)];
+    const EXAMPLE2: [(BlockShape, usize); 3] = [(T, 1), (Z, 3), (I, 4)];
+    const EXAMPLE3: [(BlockShape, usize); 8] = [
+        (Q, 0),
+        (I, 2),
+        (I, 6),
+        (I, 0),
+        (I, 6),
+        (I, 6),
+        (Q, 2),
+        (Q, 4),
+    ];
+
+    #[test]
+    fn process_example1() -> anyhow::Result<()> {
+        assert_eq!(
+            process_blocks(Grid::default(), EXAMPLE1.map(Ok))?,
+            grid![
+                [. . . . . . . . . . ],
+                [. . . . . . . . . . ],
+                [. . . . . . . . # # ],
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn play_example1() -> anyhow::Result<()> {
+        let grid = EXAMPLE1
+            .into_iter()
+            .try_fold(Grid::default(), |grid, (shape, column)| {
+                grid.play(shape, column)
+            })?;
+        assert_eq!(
+            grid,
+            process_blocks(Grid::<10, 3>::default(), EXAMPLE1.map(Ok))?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn play_huge_column_hangs_off_edge() {
+        assert_eq!(
+            Grid::<10, 20>::default().play(I, usize::MAX),
+            Err(crate::PlayError::HangsOffEdge {
+                shape: I,
+                column: usize::MAX,
+                shape_width: 4,
+                grid_width: 10,
+            })
+        );
+    }
+
+    #[test]
+    fn half_turn_flips_t() -> anyhow::Result<()> {
+        // a T placed pointing down needs room above it to turn
+        let board = Grid::<4, 4>::default();
+        assert_eq!(
+            board.play(T, 1)?,
+            grid![
+                [. . . .],
+                [. . . .],
+                [. # # #],
+                [. . # .],
+            ]
+        );
+        assert_eq!(
+            board.play_oriented(T, 1, 2)?,
+            grid![
+                [. . . .],
+                [. . . .],
+                [. . # .],
+                [. # # #],
+            ]
+        );
+        assert_eq!(board.play_oriented(T, 1, 4)?, board.play(T, 1)?);
+        Ok(())
+    }
+
+    #[test]
+    fn drop_sequence_reports_failing_move() -> anyhow::Result<()> {
+        let board = Grid::<4, 3>::default();
+        assert_eq!(
+            board.drop_sequence([(I, 0), (Q, 2)]),
+            Ok((
+                grid![
+                    [. . . .],
+                    [. . # #],
+                    [. . # #],
+                ],
+                vec![1, 0]
+            ))
+        );
+        assert_eq!(
+            board.drop_sequence([(Q, 0), (Q, 0), (Q, 0)]),
+            Err((board.play(Q, 0)?, 1))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn process_example2() -> anyhow::Result<()> {
+        assert_eq!(
+            process_blocks(Grid::default(), EXAMPLE2.map(Ok))?,
+            grid![
+                [. . . . # # # # . . ],
+                [. . . # # . . . . . ],
+                [. # # # # # . . . . ],
+                [. . # . . . . . . . ],
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn process_example3() -> anyhow::Result<()> {
+        assert_eq!(
+            process_blocks(Grid::default(), EXAMPLE3.map(Ok))?,
+            grid![
+                [. . . . . . . . . .],
+                [. . . . . . . . . .],
+                [. . # # . . . . . .],
+                [. . # # . . . . . .],
+                [# # . . # # # # # #],
+            ]
+        );
+        Ok(())
+    }
+    #[test]
+    fn highest_block_example1() -> anyhow::Result<()> {
+        assert_eq!(
+            highest_block_after_processing(Grid::<WIDTH, HEIGHT>::default(), EXAMPLE1.map(Ok))?,
+            1
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn highest_block_example2() -> anyhow::Result<()> {
+        assert_eq!(
+            highest_block_after_processing(Grid::<WIDTH, HEIGHT>::default(), EXAMPLE2.map(Ok))?,
+            4
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn highest_block_example3() -> anyhow::Result<()> {
+        assert_eq!(
+            highest_block_after_processing(Grid::<WIDTH, HEIGHT>::default(), EXAMPLE3.map(Ok))?,
+            3
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn trace_heights_example1() -> anyhow::Result<()> {
+        assert_eq!(
+            heights_after_each_block(Grid::<WIDTH, HEIGHT>::default(), EXAMPLE1.map(Ok))?,
+            [1, 1, 1]
+        );
+        assert_eq!(trace_line("I0,I4,Q8")?, [1, 1, 1]);
+        Ok(())
+    }
+
+    #[test]
+    fn long_line_is_streamed() -> anyhow::Result<()> {
+        // each repetition fills and clears two rows, so a shallow grid keeps this quick
+        let line = format!("{},I0", ["Q0,Q2,Q4,Q6,Q8"; 2000].join(","));
+        assert_eq!(
+            highest_block_after_processing(Grid::<WIDTH, 4>::default(), parse_line(&line))?,
+            1
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn play_lines_examples() -> anyhow::Result<()> {
+        let lines = ["I0,I4,Q8", "T1,Z3,I4", "Q0,I2,I6,I0,I6,I6,Q2,Q4"].map(String::from);
+        assert_eq!(
+            play_lines(lines.into_iter()).collect::<anyhow::Result<Vec<_>>>()?,
+            [1, 4, 3]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,95 @@
+//! The next queue, previewing upcoming pieces.
+
+use crate::BlockShape;
+use std::collections::VecDeque;
+
+/// Upcoming pieces, drawn from `Generator` (e.g a [SevenBag](crate::random::SevenBag)).
+///
+/// Up to `preview` pieces are kept buffered, so [PieceQueue::peek] never draws from the generator.
+/// Once the generator runs out, the buffered pieces are still handed out.
+#[derive(Debug, Clone)]
+pub struct PieceQueue<Generator> {
+    generator: Generator,
+    buffered: VecDeque<BlockShape>,
+    preview: usize,
+}
+
+impl<Generator> PieceQueue<Generator>
+where
+    Generator: Iterator<Item = BlockShape>,
+{
+    pub fn new(generator: Generator, preview: usize) -> Self {
+        let mut queue = Self {
+            generator,
+            buffered: VecDeque::with_capacity(preview),
+            preview,
+        };
+        queue.refill();
+        queue
+    }
+
+    /// The next `n` pieces, without removing them.
+    /// At most `preview` pieces are returned.
+    /// ```
+    /// use tetris::{queue::PieceQueue, BlockShape::*};
+    /// let mut queue = PieceQueue::new([I, T, Q, Z].into_iter(), 2);
+    /// assert_eq!(queue.peek(3), [I, T]);
+    /// assert_eq!(queue.pop(), Some(I));
+    /// assert_eq!(queue.peek(3), [T, Q]);
+    /// ```
+    pub fn peek(&self, n: usize) -> Vec<BlockShape> {
+        self.buffered.iter().take(n).copied().collect()
+    }
+
+    /// Take the next piece, topping the queue back up from the generator.
+    /// Returns [None] once the generator has run out and nothing is left buffered.
+    /// ```
+    /// use tetris::{queue::PieceQueue, BlockShape::*};
+    /// let mut queue = PieceQueue::new([I, T, Q, Z].into_iter(), 2);
+    /// assert_eq!(queue.pop(), Some(I));
+    /// assert_eq!(queue.pop(), Some(T));
+    /// assert_eq!(queue.pop(), Some(Q));
+    /// assert_eq!(queue.peek(2), [Z]);
+    /// assert_eq!(queue.pop(), Some(Z));
+    /// assert_eq!(queue.pop(), None);
+    /// ```
+    pub fn pop(&mut self) -> Option<BlockShape> {
+        // nothing is buffered without a preview
+        let next = self.buffered.pop_front().or_else(|| self.generator.next());
+        self.refill();
+        next
+    }
+
+    /// Buffer up to `preview` pieces, stopping early if the generator runs out.
+    fn refill(&mut self) {
+        while self.buffered.len() < self.preview {
+            let Some(next) = self.generator.next() else {
+                break;
+            };
+            self.buffered.push_back(next)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn peek_is_stable_until_pop() {
+        let mut queue = PieceQueue::new(BlockShape::ALL.into_iter().cycle(), 5);
+        let before = queue.peek(3);
+        assert_eq!(queue.peek(3), before);
+        assert_eq!(queue.pop(), Some(before[0]));
+        assert_eq!(queue.peek(3)[..2], before[1..]);
+        assert_eq!(queue.peek(3).len(), 3);
+    }
+
+    #[test]
+    fn no_preview_draws_straight_from_the_generator() {
+        let mut queue = PieceQueue::new([BlockShape::I].into_iter(), 0);
+        assert_eq!(queue.peek(1), []);
+        assert_eq!(queue.pop(), Some(BlockShape::I));
+        assert_eq!(queue.pop(), None);
+    }
+}
@@ -0,0 +1,94 @@
+//! Randomised gameplay, enabled by the `rand` feature.
+//!
+//! Everything takes an explicit [Rng], so games are reproducible given a seeded generator.
+
+use crate::{BlockShape, CellState};
+use rand::{
+    seq::{index, SliceRandom},
+    Rng,
+};
+
+/// Pick a shape uniformly at random.
+pub fn random_shape(rng: &mut impl Rng) -> BlockShape {
+    BlockShape::ALL[rng.gen_range(0..BlockShape::ALL.len())]
+}
+
+/// A row of garbage: every cell is occupied except for `holes` at random positions.
+/// # Panics
+/// - If `holes > WIDTH`
+pub fn garbage_row<const WIDTH: usize>(rng: &mut impl Rng, holes: usize) -> [CellState; WIDTH] {
+    let mut row = [CellState::Occupied; WIDTH];
+    for col_ix in index::sample(rng, WIDTH, holes) {
+        row[col_ix] = CellState::Unoccupied
+    }
+    row
+}
+
+/// The guideline randomiser: deal each of the seven shapes once, in a random order, then refill the bag.
+///
+/// This is an endless [Iterator].
+#[derive(Debug, Clone)]
+pub struct SevenBag<R> {
+    rng: R,
+    bag: [BlockShape; 7],
+    /// How many shapes in `bag` have been dealt.
+    dealt: usize,
+}
+
+impl<R: Rng> SevenBag<R> {
+    pub fn new(rng: R) -> Self {
+        Self {
+            rng,
+            bag: BlockShape::ALL,
+            dealt: BlockShape::ALL.len(),
+        }
+    }
+}
+
+impl<R: Rng> Iterator for SevenBag<R> {
+    type Item = BlockShape;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.dealt == self.bag.len() {
+            self.bag.shuffle(&mut self.rng);
+            self.dealt = 0;
+        }
+        self.dealt += 1;
+        Some(self.bag[self.dealt - 1])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::is_empty;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn fixed_seed_reproduces_shapes() {
+        let shapes = |seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            (0..100).map(|_| random_shape(&mut rng)).collect::<Vec<_>>()
+        };
+        assert_eq!(shapes(42), shapes(42));
+    }
+
+    #[test]
+    fn garbage_row_has_holes() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for holes in 0..=10 {
+            let row: [CellState; 10] = garbage_row(&mut rng, holes);
+            assert_eq!(row.iter().filter(|cell| is_empty(*cell)).count(), holes);
+        }
+    }
+
+    #[test]
+    fn seven_bag_deals_every_shape() {
+        let mut bag = SevenBag::new(StdRng::seed_from_u64(42));
+        for _ in 0..3 {
+            let mut dealt = bag.by_ref().take(7).collect::<Vec<_>>();
+            dealt.sort_by_key(|shape| *shape as u8);
+            assert_eq!(dealt, BlockShape::ALL);
+        }
+    }
+}
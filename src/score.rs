@@ -0,0 +1,294 @@
+//! Guideline scoring for line clears.
+//!
+//! Feed the number of rows cleared by each drop (e.g from [Grid::clear_solid_rows](crate::Grid::clear_solid_rows))
+//! into a [Scorer]:
+//! - Clearing 1, 2, 3 or 4 rows is worth 100, 300, 500 or 800 points, multiplied by the level.
+//! - A tetris (4 rows) immediately following another tetris is worth half as much again ("back-to-back").
+//! - Consecutive clearing drops build a combo, worth an extra 50 × combo × level points.
+//! - The level increases every 10 rows cleared.
+//! - Leaving the board empty ("perfect clear") is worth a further 800, 1200, 1800 or 2000 points,
+//!   multiplied by the level. See [Scorer::apply_clears_to].
+//!
+//! [Grid::classify_clears] names each clear, for scoring systems which distinguish more kinds of clear.
+
+use crate::{tspin::TSpinKind, Grid};
+
+/// How many rows a single drop cleared, by name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ClearKind {
+    #[default]
+    None,
+    Single,
+    Double,
+    Triple,
+    /// Four rows, which is as many as a single piece can clear.
+    Tetris,
+}
+
+/// A drop, classified by [Grid::classify_clears].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ClearEvent {
+    pub kind: ClearKind,
+    pub t_spin: TSpinKind,
+    /// The board was left empty.
+    pub perfect_clear: bool,
+}
+
+impl ClearEvent {
+    /// Whether this clear continues a back-to-back chain: a tetris, or a T-spin which cleared rows.
+    pub fn is_difficult(&self) -> bool {
+        match self.kind {
+            ClearKind::None => false,
+            ClearKind::Tetris => true,
+            _ => self.t_spin != TSpinKind::None,
+        }
+    }
+}
+
+impl<const WIDTH: usize, const HEIGHT: usize, CellT> Grid<WIDTH, HEIGHT, CellT>
+where
+    CellT: Default + PartialEq,
+{
+    /// Name the drop which cleared `cleared` rows, leaving this board,
+    /// e.g with `cleared` from [Grid::clear_solid_rows] and `t_spin` from [Grid::is_t_spin].
+    /// ```
+    /// use tetris::{grid, score::{ClearEvent, ClearKind}, tspin::TSpinKind};
+    /// let mut board = grid![
+    ///     [. . .],
+    ///     [# . .],
+    ///     [# # #],
+    /// ];
+    /// let cleared = board.clear_solid_rows();
+    /// assert_eq!(
+    ///     board.classify_clears(cleared, TSpinKind::None),
+    ///     ClearEvent {
+    ///         kind: ClearKind::Single,
+    ///         t_spin: TSpinKind::None,
+    ///         perfect_clear: false,
+    ///     }
+    /// );
+    /// ```
+    pub fn classify_clears(&self, cleared: usize, t_spin: TSpinKind) -> ClearEvent {
+        let kind = match cleared {
+            0 => ClearKind::None,
+            1 => ClearKind::Single,
+            2 => ClearKind::Double,
+            3 => ClearKind::Triple,
+            _ => ClearKind::Tetris,
+        };
+        ClearEvent {
+            kind,
+            t_spin,
+            perfect_clear: cleared > 0 && self.is_empty_board(),
+        }
+    }
+}
+
+/// Running score for a single game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scorer {
+    level: u32,
+    lines: u32,
+    score: u64,
+    /// `-1` when the last drop cleared nothing, otherwise the number of consecutive clearing drops, less one.
+    combo: i32,
+    back_to_back: bool,
+}
+
+impl Default for Scorer {
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+impl Scorer {
+    /// Start scoring at `level`.
+    pub fn new(level: u32) -> Self {
+        Self {
+            level,
+            lines: 0,
+            score: 0,
+            combo: -1,
+            back_to_back: false,
+        }
+    }
+
+    pub fn level(&self) -> u32 {
+        self.level
+    }
+
+    pub fn lines(&self) -> u32 {
+        self.lines
+    }
+
+    pub fn score(&self) -> u64 {
+        self.score
+    }
+
+    pub fn combo(&self) -> i32 {
+        self.combo
+    }
+
+    /// Record a drop which cleared `cleared` rows, returning the points awarded.
+    /// ```
+    /// use tetris::score::Scorer;
+    /// let mut scorer = Scorer::new(2);
+    /// assert_eq!(scorer.apply_clears(2), 600);
+    /// assert_eq!(scorer.apply_clears(0), 0);
+    /// assert_eq!(scorer.score(), 600);
+    /// ```
+    pub fn apply_clears(&mut self, cleared: usize) -> u64 {
+        if cleared == 0 {
+            self.combo = -1;
+            return 0;
+        }
+        self.combo += 1;
+        let level = u64::from(self.level);
+
+        let is_tetris = cleared >= 4;
+        let mut points = level
+            * match cleared {
+                1 => 100,
+                2 => 300,
+                3 => 500,
+                _ => 800,
+            };
+        if is_tetris && self.back_to_back {
+            points = points * 3 / 2;
+        }
+        self.back_to_back = is_tetris;
+        if self.combo > 0 {
+            points += 50 * self.combo as u64 * level;
+        }
+
+        let levels_before = self.lines / 10;
+        self.lines += cleared as u32;
+        self.level += self.lines / 10 - levels_before;
+        self.score += points;
+        points
+    }
+
+    /// As [Scorer::apply_clears], awarding a perfect clear bonus if `board_after_clear` is empty.
+    /// ```
+    /// use tetris::{grid, score::Scorer};
+    /// let mut scorer = Scorer::default();
+    /// let mut board = grid![
+    ///     [. . .],
+    ///     [# # #],
+    /// ];
+    /// let cleared = board.clear_solid_rows();
+    /// assert_eq!(scorer.apply_clears_to(cleared, &board), 100 + 800);
+    /// ```
+    pub fn apply_clears_to<const WIDTH: usize, const HEIGHT: usize, CellT>(
+        &mut self,
+        cleared: usize,
+        board_after_clear: &Grid<WIDTH, HEIGHT, CellT>,
+    ) -> u64
+    where
+        CellT: Default + PartialEq,
+    {
+        let level = u64::from(self.level);
+        let mut points = self.apply_clears(cleared);
+        if cleared > 0 && board_after_clear.is_empty_board() {
+            let bonus = level
+                * match cleared {
+                    1 => 800,
+                    2 => 1200,
+                    3 => 1800,
+                    _ => 2000,
+                };
+            self.score += bonus;
+            points += bonus;
+        }
+        points
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid;
+
+    #[test]
+    fn back_to_back_tetris() {
+        let mut scorer = Scorer::default();
+        assert_eq!(scorer.apply_clears(4), 800);
+        assert_eq!(scorer.apply_clears(0), 0);
+        // 800 * 1.5, no combo because the previous drop broke it
+        assert_eq!(scorer.apply_clears(4), 1200);
+        // 800 * 1.5 + 50 * 1
+        assert_eq!(scorer.apply_clears(4), 1250);
+        assert_eq!(scorer.score(), 3250);
+        assert_eq!(scorer.lines(), 12);
+        assert_eq!(scorer.level(), 2);
+    }
+
+    #[test]
+    fn non_tetris_breaks_back_to_back() {
+        let mut scorer = Scorer::default();
+        scorer.apply_clears(4);
+        scorer.apply_clears(0);
+        scorer.apply_clears(1);
+        scorer.apply_clears(0);
+        assert_eq!(scorer.apply_clears(4), 800);
+    }
+
+    #[test]
+    fn combo_sequence() {
+        let mut scorer = Scorer::default();
+        assert_eq!(scorer.apply_clears(1), 100);
+        assert_eq!(scorer.apply_clears(1), 150);
+        assert_eq!(scorer.apply_clears(2), 400);
+        assert_eq!(scorer.combo(), 2);
+        assert_eq!(scorer.apply_clears(0), 0);
+        assert_eq!(scorer.combo(), -1);
+        assert_eq!(scorer.apply_clears(1), 100);
+        assert_eq!(scorer.score(), 750);
+    }
+
+    #[test]
+    fn perfect_clear_bonus() {
+        let mut board = grid![
+            [. . . .],
+            [# # # #],
+            [# # # #],
+        ];
+        let mut scorer = Scorer::default();
+        let cleared = board.clear_solid_rows();
+        assert_eq!(scorer.apply_clears_to(cleared, &board), 300 + 1200);
+        assert_eq!(scorer.score(), 1500);
+    }
+
+    #[test]
+    fn no_bonus_with_cells_left() {
+        let mut board = grid![
+            [. . . .],
+            [# . . .],
+            [# # # #],
+        ];
+        let mut scorer = Scorer::default();
+        let cleared = board.clear_solid_rows();
+        assert_eq!(scorer.apply_clears_to(cleared, &board), 100);
+    }
+
+    #[test]
+    fn four_rows_is_a_tetris() {
+        let mut board = grid![
+            [. #],
+            [# #],
+            [# #],
+            [# #],
+            [# #],
+        ];
+        let cleared = board.clear_solid_rows();
+        let event = board.classify_clears(cleared, TSpinKind::None);
+        assert_eq!(event.kind, ClearKind::Tetris);
+        assert!(event.is_difficult());
+        assert!(!event.perfect_clear);
+        assert!(
+            Grid::<2, 1>::default()
+                .classify_clears(2, TSpinKind::Full)
+                .perfect_clear
+        );
+    }
+}
@@ -0,0 +1,174 @@
+//! A board which keeps count of the occupied cells in each row as they change,
+//! so that checking for a full row doesn't need a scan.
+
+use crate::{is_occupied, CellState, Grid};
+
+/// A [Grid] of [CellState], with the number of occupied cells in each row kept up to date.
+///
+/// Changes go through [TrackedGrid::set] and [TrackedGrid::clear], so the counts can't go stale.
+/// # Compile errors
+/// - If `WIDTH` doesn't fit in a [u16].
+/// ```compile_fail
+/// use tetris::tracked::TrackedGrid;
+/// let _ = TrackedGrid::<70_000, 1>::default();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrackedGrid<const WIDTH: usize, const HEIGHT: usize> {
+    grid: Grid<WIDTH, HEIGHT, CellState>,
+    row_counts: [u16; HEIGHT],
+}
+
+impl<const WIDTH: usize, const HEIGHT: usize> Default for TrackedGrid<WIDTH, HEIGHT> {
+    fn default() -> Self {
+        let () = Self::WIDTH_FITS_COUNT;
+        Self {
+            grid: Grid::default(),
+            row_counts: [0; HEIGHT],
+        }
+    }
+}
+
+impl<const WIDTH: usize, const HEIGHT: usize> From<Grid<WIDTH, HEIGHT, CellState>>
+    for TrackedGrid<WIDTH, HEIGHT>
+{
+    fn from(grid: Grid<WIDTH, HEIGHT, CellState>) -> Self {
+        let () = Self::WIDTH_FITS_COUNT;
+        Self {
+            grid,
+            row_counts: grid.row_fill_counts().map(|count| count as u16),
+        }
+    }
+}
+
+impl<const WIDTH: usize, const HEIGHT: usize> From<TrackedGrid<WIDTH, HEIGHT>>
+    for Grid<WIDTH, HEIGHT, CellState>
+{
+    fn from(tracked: TrackedGrid<WIDTH, HEIGHT>) -> Self {
+        tracked.grid
+    }
+}
+
+impl<const WIDTH: usize, const HEIGHT: usize> TrackedGrid<WIDTH, HEIGHT> {
+    /// Evaluated by every constructor, since each row's count is kept as a [u16].
+    const WIDTH_FITS_COUNT: () = assert!(WIDTH <= u16::MAX as usize, "rows are too wide to count");
+
+    /// The board, which can only be changed through this wrapper.
+    pub fn grid(&self) -> &Grid<WIDTH, HEIGHT, CellState> {
+        &self.grid
+    }
+
+    /// Occupy the cell at `row_ix`, `col_ix`.
+    /// # Panics
+    /// - If the cell is out of bounds.
+    pub fn set(&mut self, row_ix: usize, col_ix: usize) {
+        let cell = &mut self.grid.rows[row_ix][col_ix];
+        if !is_occupied(cell) {
+            *cell = CellState::Occupied;
+            self.row_counts[row_ix] += 1;
+        }
+    }
+
+    /// Empty the cell at `row_ix`, `col_ix`.
+    /// # Panics
+    /// - If the cell is out of bounds.
+    pub fn clear(&mut self, row_ix: usize, col_ix: usize) {
+        let cell = &mut self.grid.rows[row_ix][col_ix];
+        if is_occupied(cell) {
+            *cell = CellState::Unoccupied;
+            self.row_counts[row_ix] -= 1;
+        }
+    }
+
+    /// The number of occupied cells in row `row_ix`.
+    /// # Panics
+    /// - If `row_ix` is out of bounds.
+    pub fn row_count(&self, row_ix: usize) -> usize {
+        self.row_counts[row_ix].into()
+    }
+
+    /// Whether every cell in row `row_ix` is occupied.
+    /// # Panics
+    /// - If `row_ix` is out of bounds.
+    pub fn row_is_full(&self, row_ix: usize) -> bool {
+        self.row_count(row_ix) == WIDTH
+    }
+
+    /// As [Grid::clear_solid_rows], but finding the full rows from the counts.
+    /// ```
+    /// use tetris::{grid, tracked::TrackedGrid};
+    /// let mut tracked = TrackedGrid::from(grid![
+    ///     [. . .],
+    ///     [# . .],
+    ///     [# # .],
+    /// ]);
+    /// tracked.set(2, 2);
+    /// assert!(tracked.row_is_full(2));
+    /// assert_eq!(tracked.clear_full_rows(), 1);
+    /// assert_eq!(tracked.grid(), &grid![
+    ///     [. . .],
+    ///     [. . .],
+    ///     [# . .],
+    /// ]);
+    /// ```
+    pub fn clear_full_rows(&mut self) -> usize {
+        // compact the rows which aren't full towards the bottom, then empty what's left above them
+        let mut to_row_ix = HEIGHT;
+        for row_ix in (0..HEIGHT).rev() {
+            if !self.row_is_full(row_ix) {
+                to_row_ix -= 1;
+                self.grid.rows[to_row_ix] = self.grid.rows[row_ix];
+                self.row_counts[to_row_ix] = self.row_counts[row_ix];
+            }
+        }
+        for row_ix in 0..to_row_ix {
+            self.grid.rows[row_ix] = [CellState::Unoccupied; WIDTH];
+            self.row_counts[row_ix] = 0;
+        }
+        to_row_ix
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn setting_twice_counts_once() {
+        let mut tracked = TrackedGrid::<3, 2>::default();
+        tracked.set(1, 0);
+        tracked.set(1, 0);
+        tracked.clear(0, 0);
+        assert_eq!(tracked.row_count(1), 1);
+        assert_eq!(tracked.row_count(0), 0);
+    }
+
+    proptest! {
+        #[test]
+        fn counts_match_rescan(
+            start in any::<[[bool; 4]; 6]>(),
+            ops in prop::collection::vec((0..6usize, 0..4usize, 0..3u8), 0..64),
+        ) {
+            let mut tracked = TrackedGrid::from(Grid::<4, 6>::from(start));
+            let mut grid = Grid::<4, 6>::from(start);
+            for (row_ix, col_ix, op) in ops {
+                match op {
+                    0 => {
+                        tracked.set(row_ix, col_ix);
+                        grid.rows[row_ix][col_ix] = CellState::Occupied;
+                    }
+                    1 => {
+                        tracked.clear(row_ix, col_ix);
+                        grid.rows[row_ix][col_ix] = CellState::Unoccupied;
+                    }
+                    _ => prop_assert_eq!(tracked.clear_full_rows(), grid.clear_solid_rows()),
+                }
+                prop_assert_eq!(tracked.grid(), &grid);
+                prop_assert_eq!(
+                    tracked.row_counts.map(usize::from),
+                    grid.row_fill_counts()
+                );
+            }
+        }
+    }
+}
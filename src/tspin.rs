@@ -0,0 +1,158 @@
+//! T-spin detection, using the 3-corner rule.
+//!
+//! A T piece which locks immediately after a rotation, with at least three of the four cells
+//! diagonally adjacent to its centre occupied (or off the grid), is a T-spin.
+//! It's a [TSpinKind::Full] T-spin if both corners either side of the T's nub are filled,
+//! otherwise it's a [TSpinKind::Mini].
+
+use crate::{is_occupied, Grid};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TSpinKind {
+    #[default]
+    None,
+    Mini,
+    Full,
+}
+
+impl<const WIDTH: usize, const HEIGHT: usize, CellT> Grid<WIDTH, HEIGHT, CellT>
+where
+    CellT: Default + PartialEq,
+{
+    /// Classify `landed_t_piece`, which has come to rest on this grid, but not been combined with it.
+    /// Pieces which aren't a T are never T-spins.
+    pub fn is_t_spin(&self, landed_t_piece: &Self, was_last_move_rotation: bool) -> TSpinKind {
+        if !was_last_move_rotation {
+            return TSpinKind::None;
+        }
+        let Some((centre_row, centre_col)) = t_centre(landed_t_piece) else {
+            return TSpinKind::None;
+        };
+        let in_piece = |d_row, d_col| {
+            cell_at(landed_t_piece, centre_row, centre_col, d_row, d_col).is_some_and(is_occupied)
+        };
+        // the T's flat side is three cells, so its nub points away from the missing neighbour
+        let Some((nub_row, nub_col)) = [(-1, 0), (1, 0), (0, -1), (0, 1)]
+            .into_iter()
+            .find(|&(d_row, d_col)| !in_piece(d_row, d_col))
+            .map(|(d_row, d_col)| (-d_row, -d_col))
+        else {
+            return TSpinKind::None;
+        };
+        let filled = |d_row, d_col| {
+            cell_at(self, centre_row, centre_col, d_row, d_col).is_none_or(is_occupied)
+        };
+        let corners = [(-1, -1), (-1, 1), (1, -1), (1, 1)];
+        if corners
+            .iter()
+            .filter(|&&(d_row, d_col)| filled(d_row, d_col))
+            .count()
+            < 3
+        {
+            return TSpinKind::None;
+        }
+        // the corners either side of the nub
+        let front_filled = corners
+            .into_iter()
+            .filter(|&(d_row, d_col)| d_row == nub_row || d_col == nub_col)
+            .all(|(d_row, d_col)| filled(d_row, d_col));
+        match front_filled {
+            true => TSpinKind::Full,
+            false => TSpinKind::Mini,
+        }
+    }
+}
+
+/// The cell offset from `(row_ix, col_ix)`, or [None] if that's off the grid.
+fn cell_at<const WIDTH: usize, const HEIGHT: usize, CellT>(
+    grid: &Grid<WIDTH, HEIGHT, CellT>,
+    row_ix: usize,
+    col_ix: usize,
+    d_row: isize,
+    d_col: isize,
+) -> Option<&CellT> {
+    grid.rows
+        .get(row_ix.checked_add_signed(d_row)?)?
+        .get(col_ix.checked_add_signed(d_col)?)
+}
+
+/// The centre of a T piece is the only cell with three occupied neighbours.
+fn t_centre<const WIDTH: usize, const HEIGHT: usize, CellT>(
+    piece: &Grid<WIDTH, HEIGHT, CellT>,
+) -> Option<(usize, usize)>
+where
+    CellT: Default + PartialEq,
+{
+    let bbox = piece.bounding_box()?;
+    if (bbox.height, bbox.width) != (2, 3) && (bbox.height, bbox.width) != (3, 2) {
+        return None;
+    }
+    (bbox.top..bbox.top + bbox.height)
+        .flat_map(|row_ix| (bbox.left..bbox.left + bbox.width).map(move |col_ix| (row_ix, col_ix)))
+        .find(|&(row_ix, col_ix)| {
+            is_occupied(&piece.rows[row_ix][col_ix])
+                && [(-1, 0), (1, 0), (0, -1), (0, 1)]
+                    .into_iter()
+                    .filter(|&(d_row, d_col)| {
+                        cell_at(piece, row_ix, col_ix, d_row, d_col).is_some_and(is_occupied)
+                    })
+                    .count()
+                    == 3
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid;
+
+    const BOARD: crate::Grid<5, 4> = grid![
+        [. . . . .],
+        [# # . . .],
+        [# . . . .],
+        [# # . # #],
+    ];
+    const T_DOWN: crate::Grid<5, 4> = grid![
+        [. . . . .],
+        [. . . . .],
+        [. # # # .],
+        [. . # . .],
+    ];
+
+    #[test]
+    fn t_spin_single() {
+        assert_eq!(BOARD.is_t_spin(&T_DOWN, true), TSpinKind::Full);
+        assert_eq!(
+            (BOARD & T_DOWN).unwrap().clear_solid_rows(),
+            1,
+            "should be a single"
+        );
+    }
+
+    #[test]
+    fn no_rotation_no_t_spin() {
+        assert_eq!(BOARD.is_t_spin(&T_DOWN, false), TSpinKind::None);
+    }
+
+    #[test]
+    fn mini_when_a_front_corner_is_open() {
+        let board = grid![
+            [. . . . .],
+            [# # . # .],
+            [# . . . #],
+            [# # . . #],
+        ];
+        assert_eq!(board.is_t_spin(&T_DOWN, true), TSpinKind::Mini);
+    }
+
+    #[test]
+    fn not_a_t() {
+        let line = grid![
+            [. . . . .],
+            [. . . . .],
+            [. . . . .],
+            [. # # # #],
+        ];
+        assert_eq!(Grid::default().is_t_spin(&line, true), TSpinKind::None);
+    }
+}
@@ -0,0 +1,12 @@
+#![no_std]
+
+use tetris::{grid, CellState, Grid, WouldClobber};
+
+/// Play a piece using only the allocation-free core.
+pub fn play(board: Grid<4, 3>, piece: Grid<4, 3>) -> Option<(Grid<4, 3>, usize)> {
+    board.drop_and_clear(piece >> 1)
+}
+
+pub fn clobber() -> Result<Grid<2, 1, CellState>, WouldClobber> {
+    grid![[# .]] & grid![[# #]]
+}
@@ -5,6 +5,31 @@ const BIN_NAME: &str = "tetris";
 const EXAMPLE1: &str = "I0,I4,Q8";
 const EXAMPLE2: &str = "T1,Z3,I4";
 const EXAMPLE3: &str = "Q0,I2,I6,I0,I6,I6,Q2,Q4";
+/// input.txt from the brief
+const GIVEN_INPUT: &str = indoc!(
+    "
+    Q0
+    Q0,Q1
+    Q0,Q2,Q4,Q6,Q8
+    Q0,Q2,Q4,Q6,Q8,Q1
+    Q0,Q2,Q4,Q6,Q8,Q1,Q1
+    I0,I4,Q8
+    I0,I4,Q8,I0,I4
+    L0,J2,L4,J6,Q8
+    T0,T3
+    T0,T3,I6,I6
+    I0,I6,S4
+    T1,Z3,I4
+    L0,J3,L5,J8,T1
+    L0,J3,L5,J8,T1,T6
+    L0,J3,L5,J8,T1,T6,J2,L6,T0,T7
+    L0,J3,L5,J8,T1,T6,J2,L6,T0,T7,Q4
+    S0,S2,S4,S6
+    S0,S2,S4,S5,Q8,Q8,Q8,Q8,T1,Q1,I0,Q4
+    L0,J3,L5,J8,T1,T6,S2,Z5,T0,T7
+    Q0,I2,I6,I0,I6,I6,Q2,Q4
+    "
+);
 
 #[test]
 fn example1() -> anyhow::Result<()> {
@@ -46,35 +71,80 @@ fn all_examples() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn comments_and_blank_lines_are_skipped() -> anyhow::Result<()> {
+    Command::cargo_bin(BIN_NAME)?
+        .write_stdin(format!(
+            "# annotated examples\n{EXAMPLE1}\n\n  # another comment\n{EXAMPLE2}\n"
+        ))
+        .assert()
+        .success()
+        .stdout("1\n4\n");
+    Ok(())
+}
+
+#[test]
+fn stack_taller_than_playfield_fails() -> anyhow::Result<()> {
+    let assert = Command::cargo_bin(BIN_NAME)?
+        .write_stdin(["Q0"; 51].join(","))
+        .assert()
+        .failure();
+    let stderr = String::from_utf8_lossy(&assert.get_output().stderr);
+    assert!(stderr.contains("stack exceeded 100 rows"), "{stderr}");
+    Ok(())
+}
+
+#[test]
+fn replay_in_color() -> anyhow::Result<()> {
+    let log = std::env::temp_dir().join("tetris-replay-in-color.log");
+    std::fs::write(&log, "I,0\nT,4\n")?;
+    let output = Command::cargo_bin(BIN_NAME)?
+        .arg("--replay")
+        .arg(&log)
+        .arg("--color")
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("\x1b[46m#\x1b[0m"), "I should be cyan");
+    assert!(stdout.contains("\x1b[45m#\x1b[0m"), "T should be magenta");
+    Ok(())
+}
+
 // todo: answer not provided
 #[test]
 fn given_input_txt_parses() -> anyhow::Result<()> {
     Command::cargo_bin(BIN_NAME)?
-        .write_stdin(indoc!(
-            "
-            Q0
-            Q0,Q1
-            Q0,Q2,Q4,Q6,Q8
-            Q0,Q2,Q4,Q6,Q8,Q1
-            Q0,Q2,Q4,Q6,Q8,Q1,Q1
-            I0,I4,Q8
-            I0,I4,Q8,I0,I4
-            L0,J2,L4,J6,Q8
-            T0,T3
-            T0,T3,I6,I6
-            I0,I6,S4
-            T1,Z3,I4
-            L0,J3,L5,J8,T1
-            L0,J3,L5,J8,T1,T6
-            L0,J3,L5,J8,T1,T6,J2,L6,T0,T7
-            L0,J3,L5,J8,T1,T6,J2,L6,T0,T7,Q4
-            S0,S2,S4,S6
-            S0,S2,S4,S5,Q8,Q8,Q8,Q8,T1,Q1,I0,Q4
-            L0,J3,L5,J8,T1,T6,S2,Z5,T0,T7
-            Q0,I2,I6,I0,I6,I6,Q2,Q4
-        "
-        ))
+        .write_stdin(GIVEN_INPUT)
         .assert()
         .success();
     Ok(())
 }
+
+#[cfg(feature = "rand")]
+#[test]
+fn self_play_is_reproducible() -> anyhow::Result<()> {
+    let run = || -> anyhow::Result<_> {
+        Ok(Command::cargo_bin(BIN_NAME)?
+            .args(["--self-play", "--seed", "42", "--pieces", "50"])
+            .output()?)
+    };
+    let (first, second) = (run()?, run()?);
+    assert!(first.status.success());
+    assert_eq!(first.stdout, second.stdout);
+    Ok(())
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel_output_matches_sequential() -> anyhow::Result<()> {
+    let sequential = Command::cargo_bin(BIN_NAME)?
+        .write_stdin(GIVEN_INPUT)
+        .output()?;
+    let parallel = Command::cargo_bin(BIN_NAME)?
+        .args(["--jobs", "4"])
+        .write_stdin(GIVEN_INPUT)
+        .output()?;
+    assert!(sequential.status.success() && parallel.status.success());
+    assert_eq!(sequential.stdout, parallel.stdout);
+    Ok(())
+}
@@ -0,0 +1,7 @@
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    // with a passing case, trybuild builds rather than checks, which is when the size assertion fires
+    t.pass("tests/ui/tetromino_fits.rs");
+    t.compile_fail("tests/ui/tetromino_too_small.rs");
+}
@@ -0,0 +1,5 @@
+use tetris::{tetromino, BlockShape, CellState, Grid};
+
+fn main() {
+    let _: Grid<4, 3, CellState> = tetromino(BlockShape::I);
+}
@@ -0,0 +1,5 @@
+use tetris::{tetromino, BlockShape, CellState, Grid};
+
+fn main() {
+    let _: Grid<2, 2, CellState> = tetromino(BlockShape::Q);
+}